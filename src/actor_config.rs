@@ -0,0 +1,570 @@
+use std::{fs, io, path::Path};
+
+use macroquad::{input::KeyCode, math::vec2};
+
+use crate::{
+    simulation::Simulation,
+    soft_body::{Actor, Keybind},
+};
+
+/// Applies the actor config at `path`, if it exists. Returns the number of saved actors that
+/// no longer match the layout of their body and were skipped.
+pub fn load_from_file(simulation: &mut Simulation, path: impl AsRef<Path>) -> io::Result<usize> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(load(simulation, &text)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(error) => Err(error),
+    }
+}
+
+/// One line per rocket motor, piston, or reaction wheel, as tab separated fields:
+///
+/// `body id`, `point count`, `actor index`, `kind`, `values`, then `activate keys` and
+/// `disable keys` for each of the actor's keybinds. A rocket motor with a gimbal also stores its
+/// steer left and steer right keybinds after its enable keybind.
+///
+/// Bodies are found by their [`SoftBody::id`], since their keys change whenever the scene is
+/// loaded or a ship is pasted, so bodies without one are skipped. The point count and actor kind
+/// are stored to detect when the layout of a body has changed.
+///
+/// [`SoftBody::id`]: crate::soft_body::SoftBody::id
+pub fn save(simulation: &Simulation) -> String {
+    let mut text = String::new();
+
+    for &key in &simulation.keys {
+        let Some(soft_body) = simulation.soft_bodies.get(key) else {
+            continue;
+        };
+
+        let Some(id) = soft_body.id else {
+            continue;
+        };
+
+        for (i, actor) in soft_body.actors.iter().enumerate() {
+            let (kind, values, keybinds) = match actor {
+                Actor::RocketMotor {
+                    force,
+                    enable,
+                    gimbal,
+                    ..
+                } => (
+                    "rocket",
+                    vec![force.x, force.y],
+                    [enable]
+                        .into_iter()
+                        .chain(
+                            gimbal
+                                .iter()
+                                .flat_map(|gimbal| [&gimbal.steer_left, &gimbal.steer_right]),
+                        )
+                        .collect(),
+                ),
+                Actor::Piston { lengths, enable } => (
+                    "piston",
+                    lengths
+                        .iter()
+                        .flat_map(|&(_, off_length, on_length)| [off_length, on_length])
+                        .collect(),
+                    vec![enable],
+                ),
+                Actor::ReactionWheel {
+                    torque,
+                    enable_cw,
+                    enable_ccw,
+                } => ("wheel", vec![*torque], vec![enable_cw, enable_ccw]),
+                Actor::HabitatBubble { .. }
+                | Actor::OrientationSpring { .. }
+                | Actor::Label { .. } => {
+                    continue;
+                }
+            };
+
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}",
+                id,
+                soft_body.shape.len(),
+                i,
+                kind,
+                join(values.iter()),
+            ));
+
+            for keybind in keybinds {
+                text.push_str(&format!(
+                    "\t{}\t{}",
+                    join(
+                        keybind
+                            .activate
+                            .iter()
+                            .map(|key_code| format!("{key_code:?}"))
+                    ),
+                    join(
+                        keybind
+                            .disable
+                            .iter()
+                            .map(|key_code| format!("{key_code:?}"))
+                    ),
+                ));
+            }
+
+            text.push('\n');
+        }
+    }
+
+    text
+}
+
+/// Applies each line written by [`save`] whose actor still exists with the same kind and number
+/// of values. Returns the number of lines that were skipped.
+pub fn load(simulation: &mut Simulation, text: &str) -> usize {
+    let mut skipped = 0;
+
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if load_line(simulation, line).is_none() {
+            skipped += 1;
+        }
+    }
+
+    skipped
+}
+
+fn load_line(simulation: &mut Simulation, line: &str) -> Option<()> {
+    let fields = line.split('\t').collect::<Vec<_>>();
+
+    let [id, point_count, index, kind, values, keybinds @ ..] = fields.as_slice() else {
+        return None;
+    };
+
+    let key = simulation.body_by_id(id.parse().ok()?)?;
+    let soft_body = simulation.soft_bodies.get_mut(key)?;
+
+    if soft_body.shape.len() != point_count.parse::<usize>().ok()? {
+        return None;
+    }
+
+    let values = values
+        .split_whitespace()
+        .map(|value| value.parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    if keybinds.len() % 2 != 0 {
+        return None;
+    }
+
+    let keybinds = keybinds
+        .chunks(2)
+        .map(|keys| {
+            Some(Keybind {
+                activate: parse_key_codes(keys[0])?,
+                disable: parse_key_codes(keys[1])?,
+                forced: None,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match (
+        soft_body.actors.get_mut(index.parse::<usize>().ok()?)?,
+        *kind,
+    ) {
+        (
+            Actor::RocketMotor {
+                force,
+                enable,
+                max_particle_time,
+                gimbal,
+                ..
+            },
+            "rocket",
+        ) => {
+            let &[x, y] = values.as_slice() else {
+                return None;
+            };
+
+            match (keybinds.as_slice(), gimbal) {
+                ([keybind], None) => *enable = keybind.clone(),
+                ([keybind, steer_left, steer_right], Some(gimbal)) => {
+                    *enable = keybind.clone();
+                    gimbal.steer_left = steer_left.clone();
+                    gimbal.steer_right = steer_right.clone();
+                }
+                _ => return None,
+            }
+
+            *force = vec2(x, y);
+            // Matches the exhaust to the new thrust, as the actor editor does
+            *max_particle_time = 0.5 / force.length();
+        }
+        (Actor::Piston { lengths, enable }, "piston") => {
+            let [keybind] = keybinds.as_slice() else {
+                return None;
+            };
+
+            if values.len() != lengths.len() * 2 {
+                return None;
+            }
+
+            for ((_, off_length, on_length), values) in lengths.iter_mut().zip(values.chunks(2)) {
+                *off_length = values[0];
+                *on_length = values[1];
+            }
+
+            *enable = keybind.clone();
+        }
+        (
+            Actor::ReactionWheel {
+                torque,
+                enable_cw,
+                enable_ccw,
+            },
+            "wheel",
+        ) => {
+            let (&[new_torque], [cw, ccw]) = (values.as_slice(), keybinds.as_slice()) else {
+                return None;
+            };
+
+            *torque = new_torque;
+            *enable_cw = cw.clone();
+            *enable_ccw = ccw.clone();
+        }
+        _ => return None,
+    }
+
+    Some(())
+}
+
+fn join(items: impl Iterator<Item = impl ToString>) -> String {
+    items
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_key_codes(text: &str) -> Option<Vec<KeyCode>> {
+    text.split_whitespace().map(parse_key_code).collect()
+}
+
+pub fn parse_key_code(name: &str) -> Option<KeyCode> {
+    KEY_CODES
+        .iter()
+        .copied()
+        .find(|key_code| format!("{key_code:?}") == name)
+}
+
+/// Every [`KeyCode`], since they can't be converted back from their names or values
+const KEY_CODES: [KeyCode; 122] = {
+    use KeyCode::*;
+    [
+        Space,
+        Apostrophe,
+        Comma,
+        Minus,
+        Period,
+        Slash,
+        Key0,
+        Key1,
+        Key2,
+        Key3,
+        Key4,
+        Key5,
+        Key6,
+        Key7,
+        Key8,
+        Key9,
+        Semicolon,
+        Equal,
+        A,
+        B,
+        C,
+        D,
+        E,
+        F,
+        G,
+        H,
+        I,
+        J,
+        K,
+        L,
+        M,
+        N,
+        O,
+        P,
+        Q,
+        R,
+        S,
+        T,
+        U,
+        V,
+        W,
+        X,
+        Y,
+        Z,
+        LeftBracket,
+        Backslash,
+        RightBracket,
+        GraveAccent,
+        World1,
+        World2,
+        Escape,
+        Enter,
+        Tab,
+        Backspace,
+        Insert,
+        Delete,
+        Right,
+        Left,
+        Down,
+        Up,
+        PageUp,
+        PageDown,
+        Home,
+        End,
+        CapsLock,
+        ScrollLock,
+        NumLock,
+        PrintScreen,
+        Pause,
+        F1,
+        F2,
+        F3,
+        F4,
+        F5,
+        F6,
+        F7,
+        F8,
+        F9,
+        F10,
+        F11,
+        F12,
+        F13,
+        F14,
+        F15,
+        F16,
+        F17,
+        F18,
+        F19,
+        F20,
+        F21,
+        F22,
+        F23,
+        F24,
+        F25,
+        Kp0,
+        Kp1,
+        Kp2,
+        Kp3,
+        Kp4,
+        Kp5,
+        Kp6,
+        Kp7,
+        Kp8,
+        Kp9,
+        KpDecimal,
+        KpDivide,
+        KpMultiply,
+        KpSubtract,
+        KpAdd,
+        KpEnter,
+        KpEqual,
+        LeftShift,
+        LeftControl,
+        LeftAlt,
+        LeftSuper,
+        RightShift,
+        RightControl,
+        RightAlt,
+        RightSuper,
+        Menu,
+        Back,
+        Unknown,
+    ]
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soft_body::{Gimbal, SoftBodyBuilder};
+
+    fn keybind(activate: KeyCode, disable: KeyCode) -> Keybind {
+        Keybind {
+            activate: vec![activate],
+            disable: vec![disable],
+            forced: None,
+        }
+    }
+
+    fn triangle_with(actor: Actor) -> Simulation {
+        let mut simulation = Simulation::new();
+
+        let key = simulation.soft_bodies.insert(
+            SoftBodyBuilder::default()
+                .point(0.0, 0.0)
+                .point(1.0, 0.0)
+                .point(0.0, 1.0)
+                .with_actor(actor)
+                .id(7)
+                .build(),
+        );
+        simulation.keys.push(key);
+
+        simulation
+    }
+
+    fn wheel_simulation(torque: f32, enable_cw: Keybind, enable_ccw: Keybind) -> Simulation {
+        triangle_with(Actor::ReactionWheel {
+            torque,
+            enable_cw,
+            enable_ccw,
+        })
+    }
+
+    fn gimballed_rocket_simulation(steer_left: Keybind, steer_right: Keybind) -> Simulation {
+        let mut actor = Actor::new_rocket_motor(0);
+
+        if let Actor::RocketMotor { gimbal, .. } = &mut actor {
+            *gimbal = Some(Gimbal {
+                steer_left,
+                steer_right,
+                ..Default::default()
+            });
+        }
+
+        triangle_with(actor)
+    }
+
+    #[test]
+    fn reaction_wheels_round_trip() {
+        let saved = save(&wheel_simulation(
+            3.0,
+            keybind(KeyCode::E, KeyCode::LeftShift),
+            keybind(KeyCode::Q, KeyCode::LeftControl),
+        ));
+
+        let mut simulation = wheel_simulation(
+            1.0,
+            keybind(KeyCode::A, KeyCode::B),
+            keybind(KeyCode::C, KeyCode::D),
+        );
+
+        assert_eq!(load(&mut simulation, &saved), 0);
+
+        let key = simulation.keys[0];
+        let Actor::ReactionWheel {
+            torque,
+            enable_cw,
+            enable_ccw,
+        } = &simulation.soft_bodies[key].actors[0]
+        else {
+            panic!("expected a reaction wheel");
+        };
+
+        assert_eq!(*torque, 3.0);
+        assert_eq!(enable_cw.activate, [KeyCode::E]);
+        assert_eq!(enable_cw.disable, [KeyCode::LeftShift]);
+        assert_eq!(enable_ccw.activate, [KeyCode::Q]);
+        assert_eq!(enable_ccw.disable, [KeyCode::LeftControl]);
+    }
+
+    #[test]
+    fn gimbal_keybinds_round_trip() {
+        let saved = save(&gimballed_rocket_simulation(
+            keybind(KeyCode::J, KeyCode::K),
+            keybind(KeyCode::L, KeyCode::M),
+        ));
+
+        let mut simulation = gimballed_rocket_simulation(
+            keybind(KeyCode::A, KeyCode::B),
+            keybind(KeyCode::C, KeyCode::D),
+        );
+
+        assert_eq!(load(&mut simulation, &saved), 0);
+
+        let key = simulation.keys[0];
+        let Actor::RocketMotor {
+            gimbal: Some(gimbal),
+            ..
+        } = &simulation.soft_bodies[key].actors[0]
+        else {
+            panic!("expected a gimballed rocket motor");
+        };
+
+        assert_eq!(gimbal.steer_left.activate, [KeyCode::J]);
+        assert_eq!(gimbal.steer_left.disable, [KeyCode::K]);
+        assert_eq!(gimbal.steer_right.activate, [KeyCode::L]);
+        assert_eq!(gimbal.steer_right.disable, [KeyCode::M]);
+    }
+
+    #[test]
+    fn gimbal_keybinds_dont_load_onto_a_rocket_without_one() {
+        let saved = save(&gimballed_rocket_simulation(
+            keybind(KeyCode::J, KeyCode::K),
+            keybind(KeyCode::L, KeyCode::M),
+        ));
+
+        let mut simulation = triangle_with(Actor::new_rocket_motor(0));
+
+        assert_eq!(load(&mut simulation, &saved), 1);
+    }
+
+    #[test]
+    fn configs_follow_the_id_rather_than_the_key() {
+        let saved = save(&wheel_simulation(
+            3.0,
+            keybind(KeyCode::E, KeyCode::LeftShift),
+            keybind(KeyCode::Q, KeyCode::LeftControl),
+        ));
+
+        // Another wheel without an id is inserted first, so the keys don't line up
+        let mut simulation = Simulation::new();
+        let mut wheel_simulation = wheel_simulation(
+            1.0,
+            keybind(KeyCode::A, KeyCode::B),
+            keybind(KeyCode::C, KeyCode::D),
+        );
+        let mut without_id = wheel_simulation.soft_bodies[wheel_simulation.keys[0]].clone();
+        without_id.id = None;
+
+        let other = simulation.soft_bodies.insert(without_id);
+        let key = simulation.soft_bodies.insert(
+            wheel_simulation
+                .soft_bodies
+                .remove(wheel_simulation.keys[0])
+                .unwrap(),
+        );
+        simulation.keys = vec![other, key];
+
+        assert_eq!(load(&mut simulation, &saved), 0);
+
+        let torque_of = |key| match simulation.soft_bodies[key].actors[0] {
+            Actor::ReactionWheel { torque, .. } => torque,
+            _ => panic!("expected a reaction wheel"),
+        };
+
+        assert_eq!(torque_of(key), 3.0);
+        assert_eq!(torque_of(other), 1.0);
+        assert_eq!(save(&simulation).lines().count(), 1);
+    }
+
+    #[test]
+    fn loaded_rocket_thrust_updates_the_exhaust() {
+        let mut actor = Actor::new_rocket_motor(0);
+        if let Actor::RocketMotor { force, .. } = &mut actor {
+            *force = vec2(0.0, 100.0);
+        }
+        let saved = save(&triangle_with(actor));
+
+        let mut simulation = triangle_with(Actor::new_rocket_motor(0));
+
+        assert_eq!(load(&mut simulation, &saved), 0);
+
+        let Actor::RocketMotor {
+            force,
+            max_particle_time,
+            ..
+        } = simulation.soft_bodies[simulation.keys[0]].actors[0]
+        else {
+            panic!("expected a rocket motor");
+        };
+
+        assert_eq!(force, vec2(0.0, 100.0));
+        assert_eq!(max_particle_time, 0.5 / 100.0);
+    }
+}
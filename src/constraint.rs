@@ -8,17 +8,28 @@ use crate::{
 
 #[derive(Clone, Debug)]
 pub enum Constraint {
-    HoldTogether { points: Vec<PointHandle> },
+    HoldTogether {
+        points: Vec<PointHandle>,
+        /// The furthest distance a point was moved the last time the constraint was applied
+        last_correction: f32,
+    },
 }
 
 impl Constraint {
     pub fn apply_to_soft_bodies(&mut self, soft_bodies: &mut HopSlotMap<SoftBodyKey, SoftBody>) {
         match self {
-            Constraint::HoldTogether { points } => {
+            Constraint::HoldTogether {
+                points,
+                last_correction,
+            } => {
                 let mut total_mass = 0.0;
                 let mut total_momentum = Vec2::ZERO;
                 let mut total_mass_moment = Vec2::ZERO;
 
+                // Points of frozen soft bodies act as if they had infinite mass
+                let mut frozen_count = 0;
+                let mut frozen_position_sum = Vec2::ZERO;
+
                 let mut i = 0;
                 while i < points.len() {
                     let Some(point) = points[i].get(soft_bodies) else {
@@ -35,15 +46,33 @@ impl Constraint {
                     total_momentum += point.velocity * point.mass;
                     total_mass_moment += point.position * point.mass;
 
+                    if soft_bodies[points[i].soft_body].frozen {
+                        frozen_count += 1;
+                        frozen_position_sum += point.position;
+                    }
+
                     i += 1;
                 }
 
-                let average_velocity = total_momentum / total_mass;
-                let average_position = total_mass_moment / total_mass;
+                let (average_velocity, average_position) = if frozen_count > 0 {
+                    (Vec2::ZERO, frozen_position_sum / frozen_count as f32)
+                } else {
+                    (total_momentum / total_mass, total_mass_moment / total_mass)
+                };
+
+                *last_correction = 0.0;
 
                 for handle in points {
+                    // Frozen points stay where they are, only the free ones are pulled to them
+                    if soft_bodies[handle.soft_body].frozen {
+                        continue;
+                    }
+
                     let point = handle.get_mut(soft_bodies).unwrap();
 
+                    *last_correction =
+                        last_correction.max(point.position.distance(average_position));
+
                     point.position = average_position;
                     point.velocity = average_velocity;
                 }
@@ -58,7 +87,7 @@ impl Constraint {
         keys_to_replace: &mut Vec<ConstraintKey>,
     ) {
         match self {
-            Constraint::HoldTogether { points } => {
+            Constraint::HoldTogether { points, .. } => {
                 let mut i = 0;
                 while i < points.len() {
                     let Some(point) = points[i].get_mut(soft_bodies) else {
@@ -86,7 +115,7 @@ impl Constraint {
         points_regrouped: &mut Vec<PointHandle>,
     ) {
         match self {
-            Constraint::HoldTogether { points } => {
+            Constraint::HoldTogether { points, .. } => {
                 for point_handle in points {
                     let Some(point) = point_handle.get_mut(soft_bodies) else {
                         continue;
@@ -105,7 +134,24 @@ impl Constraint {
 
     pub fn is_empty(&self) -> bool {
         match self {
-            Constraint::HoldTogether { points } => points.len() <= 1,
+            Constraint::HoldTogether { points, .. } => points.len() <= 1,
+        }
+    }
+
+    /// Whether any of the points held by the constraint are part of `soft_body`
+    pub fn involves_soft_body(&self, soft_body: SoftBodyKey) -> bool {
+        match self {
+            Constraint::HoldTogether { points, .. } => {
+                points.iter().any(|handle| handle.soft_body == soft_body)
+            }
+        }
+    }
+
+    pub fn last_correction(&self) -> f32 {
+        match self {
+            Constraint::HoldTogether {
+                last_correction, ..
+            } => *last_correction,
         }
     }
 }
@@ -134,3 +180,57 @@ impl PointHandle {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soft_body::SoftBodyBuilder;
+    use macroquad::math::vec2;
+
+    /// A unit triangle with its first point at `offset`
+    fn triangle(offset: Vec2, frozen: bool) -> SoftBody {
+        let mut soft_body = SoftBodyBuilder::default()
+            .offset_ex(offset)
+            .point(0.0, 0.0)
+            .point(1.0, 0.0)
+            .point(0.0, 1.0)
+            .build();
+        soft_body.frozen = frozen;
+        soft_body
+    }
+
+    #[test]
+    fn frozen_points_stay_put_while_free_ones_join_them() {
+        let mut soft_bodies = HopSlotMap::with_key();
+
+        let offsets = [vec2(0.0, 0.0), vec2(0.5, 0.0), vec2(3.0, 3.0)];
+        let keys = [
+            soft_bodies.insert(triangle(offsets[0], true)),
+            soft_bodies.insert(triangle(offsets[1], true)),
+            soft_bodies.insert(triangle(offsets[2], false)),
+        ];
+
+        for &soft_body in &keys {
+            soft_bodies[soft_body].shape[0].0.constraint = Some(ConstraintKey::default());
+        }
+
+        let mut constraint = Constraint::HoldTogether {
+            points: keys
+                .iter()
+                .map(|&soft_body| PointHandle {
+                    soft_body,
+                    index: 0,
+                })
+                .collect(),
+            last_correction: 0.0,
+        };
+        constraint.apply_to_soft_bodies(&mut soft_bodies);
+
+        assert_eq!(soft_bodies[keys[0]].shape[0].0.position, offsets[0]);
+        assert_eq!(soft_bodies[keys[1]].shape[0].0.position, offsets[1]);
+        assert_eq!(
+            soft_bodies[keys[2]].shape[0].0.position,
+            (offsets[0] + offsets[1]) / 2.0,
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use macroquad::{
+    camera::{self, Camera2D},
+    color::Color,
+    math::vec2,
+    text, window,
+};
+
+use crate::{soft_body::BoundingBox, utils};
+
+/// The distance between the thin lines, in world units
+pub const MINOR_SPACING: f32 = 1.0;
+/// The distance between the bold lines, which are labeled with their coordinate
+pub const MAJOR_SPACING: f32 = 5.0;
+
+pub const MINOR_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.05);
+pub const MAJOR_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.15);
+pub const LABEL_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.4);
+
+/// In pixels
+pub const MINOR_THICKNESS: f32 = 1.0;
+/// In pixels
+pub const MAJOR_THICKNESS: f32 = 2.0;
+pub const LABEL_FONT_SIZE: f32 = 16.0;
+
+/// Draws the lines of the grid which cross `view`, which should be
+/// [`utils::visible_world_rect`]. The camera must already be set.
+pub fn draw_grid(view: BoundingBox) {
+    let pixel_size = view.size.y / window::screen_height();
+    let max_corner = view.max_corner();
+
+    for (position, major) in lines_in_range(view.min_corner.x, max_corner.x) {
+        let (thickness, color) = line_style(major);
+
+        utils::draw_line_unscaled(
+            vec2(position, view.min_corner.y),
+            vec2(position, max_corner.y),
+            thickness * pixel_size,
+            color,
+        );
+    }
+
+    for (position, major) in lines_in_range(view.min_corner.y, max_corner.y) {
+        let (thickness, color) = line_style(major);
+
+        utils::draw_line_unscaled(
+            vec2(view.min_corner.x, position),
+            vec2(max_corner.x, position),
+            thickness * pixel_size,
+            color,
+        );
+    }
+}
+
+/// Labels each bold line of the grid with its coordinate, along the bottom and left edges of the
+/// screen. Text is drawn in screen space so it stays the same size while zooming, so the camera is
+/// set back to `camera` afterwards.
+pub fn draw_labels(camera: &Camera2D, view: BoundingBox) {
+    let max_corner = view.max_corner();
+
+    camera::set_default_camera();
+
+    for (position, major) in lines_in_range(view.min_corner.x, max_corner.x) {
+        if major {
+            let screen_position = utils::world_to_screen(camera, vec2(position, 0.0));
+
+            text::draw_text(
+                &format!("{position}"),
+                screen_position.x + 4.0,
+                window::screen_height() - 4.0,
+                LABEL_FONT_SIZE,
+                LABEL_COLOR,
+            );
+        }
+    }
+
+    for (position, major) in lines_in_range(view.min_corner.y, max_corner.y) {
+        if major {
+            let screen_position = utils::world_to_screen(camera, vec2(0.0, position));
+
+            text::draw_text(
+                &format!("{position}"),
+                4.0,
+                screen_position.y - 4.0,
+                LABEL_FONT_SIZE,
+                LABEL_COLOR,
+            );
+        }
+    }
+
+    camera::set_camera(camera);
+}
+
+/// The coordinate of every line between `min` and `max`, and whether it is a bold one
+fn lines_in_range(min: f32, max: f32) -> impl Iterator<Item = (f32, bool)> {
+    let first = (min / MINOR_SPACING).ceil() as i64;
+    let last = (max / MINOR_SPACING).floor() as i64;
+    let major_every = (MAJOR_SPACING / MINOR_SPACING).round() as i64;
+
+    (first..=last).map(move |i| (i as f32 * MINOR_SPACING, i % major_every == 0))
+}
+
+fn line_style(major: bool) -> (f32, Color) {
+    if major {
+        (MAJOR_THICKNESS, MAJOR_COLOR)
+    } else {
+        (MINOR_THICKNESS, MINOR_COLOR)
+    }
+}
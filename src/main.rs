@@ -1,30 +1,59 @@
+pub mod actor_config;
 pub mod constraint;
+pub mod grid;
 pub mod loop_crafting;
 pub mod particle;
+pub mod prefabs;
+pub mod scenario;
+pub mod scene_file;
+pub mod ship_clipboard;
 pub mod simulation;
 pub mod soft_body;
 pub mod stars;
 pub mod utils;
 
-use std::f32::consts::{SQRT_2, TAU};
-
 use macroquad::{
     camera::{self, Camera2D},
-    input::{self, KeyCode},
+    input,
     math::{Vec2, vec2},
+    rand,
     window::{self, Conf},
 };
 
 use crate::{
-    simulation::Simulation,
-    soft_body::{
-        Actor, AngularSpring, AttatchmentPointHandle, ConnectionState, Keybind, LinearSpring,
-        SoftBody, SoftBodyBuilder, SpringIndex,
-    },
+    scenario::Scenario,
+    simulation::{SceneCommand, Simulation},
+    soft_body::{AttatchmentPointHandle, BoundingBox},
+    stars::AddOffset,
 };
 
 const START_IN_FULLSCREEN: bool = true;
 
+const ACTOR_CONFIG_PATH: &str = "actor_config.txt";
+/// A ship copied with [`ship_clipboard`] or a [`Scenario`] to start with instead of
+/// [`assemble_simulation`]. Can have comments and include other ships, see [`scene_file`]
+const SCENE_PATH: &str = "scene.txt";
+/// Where the camera was looking and how far it was zoomed out, so the view is kept across restarts
+const CAMERA_PATH: &str = "camera.txt";
+/// In seconds. The camera moves almost every frame while following a ship, so it is written at most
+/// this often.
+const CAMERA_SAVE_INTERVAL: f64 = 1.0;
+/// Reloads the scene in debug builds, so changes to [`SCENE_PATH`] can be seen without restarting
+#[cfg(debug_assertions)]
+const RELOAD_SCENE_KEY: input::KeyCode = input::KeyCode::F8;
+
+/// How quickly the zoom eases towards where the scroll wheel has set it. See [`utils::exp_decay`]
+const ZOOM_SMOOTHING: f32 = 15.0;
+
+/// Keeps lines about as many pixels thick at every zoom. See [`utils::set_line_scale_for_zoom`]
+const SCALE_LINES_WITH_ZOOM: bool = true;
+
+const SCREEN_SHAKE: bool = true;
+/// How far the camera shakes per unit of collision impulse, as a fraction of the screen height
+const SCREEN_SHAKE_SCALE: f32 = 0.002;
+const MAXIMUM_SCREEN_SHAKE: f32 = 0.02;
+const SCREEN_SHAKE_DECAY: f32 = 10.0;
+
 fn config() -> Conf {
     Conf {
         window_title: "Jello Space Pond".to_owned(),
@@ -50,20 +79,43 @@ async fn main() {
         });
     });
 
-    let stars = stars::from_seed(142);
+    let mut stars = stars::from_seed(142, Default::default());
 
-    let mut simulation = assemble_simulation();
+    let mut simulation = load_scene();
+    load_actor_config(&mut simulation);
+
+    let mut saved_actor_config = actor_config::save(&simulation);
 
     let zoom_speed = 1.1f32;
-    let mut screen_height = 10.0;
+    let (target, mut screen_height) = match std::fs::read_to_string(CAMERA_PATH) {
+        Ok(text) => load_camera(&text).unwrap_or_else(|| {
+            eprintln!("Failed to load {CAMERA_PATH}: it is malformed");
+            (Vec2::ZERO, 10.0)
+        }),
+        Err(error) => {
+            if error.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("Failed to load {CAMERA_PATH}: {error}");
+            }
+            (Vec2::ZERO, 10.0)
+        }
+    };
     let mut camera = Camera2D {
+        target,
         zoom: -2.0 / Vec2::splat(screen_height),
         ..Default::default()
     };
 
+    // Scrolling sets this, and `screen_height` follows it smoothly
+    let mut target_screen_height = screen_height;
+
+    let mut saved_camera = save_camera(camera.target, screen_height);
+    let mut camera_save_time = macroquad::time::get_time();
+
     let mut fullscreen = START_IN_FULLSCREEN;
     let mut debug = false;
-    let running = true;
+    let mut stress_map = false;
+    let mut show_grid = false;
+    let mut running = true;
 
     let ticks_per_second = 120.0;
 
@@ -72,29 +124,78 @@ async fn main() {
 
     let mut tick_time = 0.0;
 
+    let screen_shake_rng = rand::RandGenerator::new();
+    screen_shake_rng.srand(0);
+    let mut screen_shake = 0.0;
+
+    // So the actor config can be saved before closing
+    input::prevent_quit();
+
     loop {
-        if input::is_key_pressed(KeyCode::F11) {
-            fullscreen ^= true;
-            macroquad::window::set_fullscreen(fullscreen);
+        if input::is_quit_requested() {
+            save_actor_config(&simulation, &mut saved_actor_config);
+            break;
         }
 
-        if input::is_key_pressed(KeyCode::F3) {
-            debug ^= true;
-        }
+        if simulation.input_state.controls_focus.is_none() {
+            if input::is_key_pressed(simulation.controls.fullscreen) {
+                fullscreen ^= true;
+                macroquad::window::set_fullscreen(fullscreen);
+            }
+
+            if input::is_key_pressed(simulation.controls.debug) {
+                debug ^= true;
+            }
+
+            if input::is_key_pressed(simulation.controls.stress_map) {
+                stress_map ^= true;
+            }
+
+            if input::is_key_pressed(simulation.controls.grid) {
+                show_grid ^= true;
+            }
+
+            if input::is_key_pressed(simulation.controls.velocities) {
+                simulation.show_velocities ^= true;
+            }
+
+            if input::is_key_pressed(simulation.controls.pause) {
+                running ^= true;
+            }
+
+            if input::is_key_pressed(simulation.controls.clear) {
+                simulation.input_state.confirming = Some(SceneCommand::Clear);
+            }
+
+            if input::is_key_pressed(simulation.controls.reset) {
+                simulation.input_state.confirming = Some(SceneCommand::Reset);
+            }
+
+            #[cfg(debug_assertions)]
+            if input::is_key_pressed(RELOAD_SCENE_KEY) {
+                let controls = simulation.controls;
 
-        // if input::is_key_pressed(KeyCode::Space) {
-        //     running ^= true;
-        // }
+                simulation = load_scene();
+                load_actor_config(&mut simulation);
+                simulation.controls = controls;
+            }
+        }
 
         let input = input::mouse_wheel().1.clamp(-1.0, 1.0);
 
         if input.abs() > f32::EPSILON {
-            screen_height *= zoom_speed.powf(-input);
-            screen_height = screen_height.clamp(10.0, 100.0);
-
-            camera.zoom = -2.0 / Vec2::splat(screen_height);
+            target_screen_height *= zoom_speed.powf(-input);
+            target_screen_height = target_screen_height.clamp(10.0, 100.0);
         }
 
+        screen_height = utils::exp_decay(
+            screen_height,
+            target_screen_height,
+            ZOOM_SMOOTHING,
+            macroquad::time::get_frame_time(),
+        );
+        camera.zoom = -2.0 / Vec2::splat(screen_height);
+
         // let mut input = vec2(0.0, 0.0);
         //
         // input.x += input::is_key_down(KeyCode::D) as u8 as f32;
@@ -106,8 +207,38 @@ async fn main() {
 
         utils::update_camera_aspect_ratio(&mut camera);
 
+        simulation.show_phase_timings = debug;
+
+        // Keybinds and actors are only changed in the editor of their soft body, so the actor
+        // config is saved when that editor is closed, or when the window is
+        let edited_soft_body = (simulation.input_state.editing)
+            .then_some(simulation.input_state.selected_soft_body)
+            .flatten();
+
         simulation.update_input(&camera, macroquad::time::get_frame_time());
 
+        if edited_soft_body.is_some()
+            && edited_soft_body
+                != (simulation.input_state.editing)
+                    .then_some(simulation.input_state.selected_soft_body)
+                    .flatten()
+        {
+            save_actor_config(&simulation, &mut saved_actor_config);
+        }
+
+        let camera_state = save_camera(camera.target, target_screen_height);
+
+        if camera_state != saved_camera
+            && macroquad::time::get_time() - camera_save_time >= CAMERA_SAVE_INTERVAL
+        {
+            if let Err(error) = std::fs::write(CAMERA_PATH, &camera_state) {
+                eprintln!("Failed to save {CAMERA_PATH}: {error}");
+            }
+
+            saved_camera = camera_state;
+            camera_save_time = macroquad::time::get_time();
+        }
+
         if running {
             tick_time += macroquad::time::get_frame_time() * ticks_per_second;
 
@@ -124,104 +255,170 @@ async fn main() {
             tick_time = tick_time.min(1.0);
         }
 
-        camera::set_camera(&camera);
+        if let Some(offset) = simulation.recenter() {
+            camera.target += offset;
+            stars.add_offset([offset.x, offset.y].into());
+        }
 
-        let bounding_box = utils::bounding_box_of_camera(&camera);
+        let view = utils::visible_world_rect(&camera);
 
-        stars::draw_stars_in_area(
-            &stars,
-            [
-                [bounding_box.min_corner.x, bounding_box.min_corner.y].into(),
-                [bounding_box.max_corner().x, bounding_box.max_corner().y].into(),
-            ],
+        for event in simulation.drain_collision_events() {
+            if SCREEN_SHAKE && view.is_point_within_distance(event.position, screen_height / 2.0) {
+                screen_shake += event.impulse * SCREEN_SHAKE_SCALE;
+            }
+        }
+
+        // Nothing here reacts to bodies entering or exiting triggers, but the events are still
+        // taken every frame so they don't pile up
+        simulation.drain_trigger_events().for_each(drop);
+
+        screen_shake = utils::exp_decay(
+            screen_shake.min(MAXIMUM_SCREEN_SHAKE),
+            0.0,
+            SCREEN_SHAKE_DECAY,
+            macroquad::time::get_frame_time(),
         );
 
-        simulation.draw(debug, bounding_box);
+        // Only moves the camera while drawing
+        let screen_shake_offset = vec2(
+            screen_shake_rng.gen_range(-1.0, 1.0),
+            screen_shake_rng.gen_range(-1.0, 1.0),
+        ) * screen_shake
+            * screen_height;
+
+        camera.target += screen_shake_offset;
+
+        camera::set_camera(&camera);
+
+        if SCALE_LINES_WITH_ZOOM {
+            utils::set_line_scale_for_zoom(screen_height);
+        }
+
+        let bounding_box = utils::visible_world_rect(&camera);
+
+        let star_area = [
+            [bounding_box.min_corner.x, bounding_box.min_corner.y].into(),
+            [bounding_box.max_corner().x, bounding_box.max_corner().y].into(),
+        ];
+
+        stars::draw_nebula_in_area(&stars, star_area);
+
+        stars::draw_stars_in_area(&stars, star_area, macroquad::time::get_time() as f32);
+
+        if show_grid {
+            grid::draw_grid(bounding_box);
+            grid::draw_labels(&camera, bounding_box);
+        }
+
+        simulation.draw(debug, stress_map, bounding_box);
+
+        camera.target -= screen_shake_offset;
 
         window::next_frame().await;
     }
 }
 
-fn assemble_simulation() -> Simulation {
-    let mut simulation = Simulation::new();
+/// Applies the saved keybinds and actor settings to the bodies of a freshly loaded scene
+fn load_actor_config(simulation: &mut Simulation) {
+    match actor_config::load_from_file(simulation, ACTOR_CONFIG_PATH) {
+        Ok(0) => (),
+        Ok(skipped) => eprintln!("Skipped {skipped} saved actors that no longer match their body"),
+        Err(error) => eprintln!("Failed to load {ACTOR_CONFIG_PATH}: {error}"),
+    }
+}
 
-    simulation
-        .soft_bodies
-        .insert(habitat_bubble(vec2(-5.0, 0.0)));
+/// Writes the actor config of `simulation` to [`ACTOR_CONFIG_PATH`] if it isn't
+/// `saved_actor_config` already
+fn save_actor_config(simulation: &Simulation, saved_actor_config: &mut String) {
+    let actor_config = actor_config::save(simulation);
 
-    let diagonal_spring = LinearSpring {
-        target_distance: SQRT_2,
-        ..Default::default()
-    };
+    if actor_config != *saved_actor_config {
+        if let Err(error) = std::fs::write(ACTOR_CONFIG_PATH, &actor_config) {
+            eprintln!("Failed to save {ACTOR_CONFIG_PATH}: {error}");
+        }
 
-    let slight_diagonal_spring = LinearSpring {
-        target_distance: vec2(1.0, 1.0 / 3.0).length(),
-        ..Default::default()
-    };
+        *saved_actor_config = actor_config;
+    }
+}
 
-    let orthogonal_spring = LinearSpring {
-        target_distance: 1.0 / 3.0,
-        ..Default::default()
-    };
+/// Writes the target and the height of the view in world units of the camera, for [`CAMERA_PATH`]
+pub fn save_camera(target: Vec2, screen_height: f32) -> String {
+    format!("{} {} {}", target.x, target.y, screen_height)
+}
 
-    let piston_spring = LinearSpring {
-        target_distance: 1.0 / 3.0,
-        force_constant: 500.0,
-        damping: 100.0,
-        maximum_force: 0.25,
-        destroy_on_maximum: false,
-        ..Default::default()
+/// Reads a camera written by [`save_camera`], returning its target and the height of its view.
+/// Returns `None` if the text is malformed.
+pub fn load_camera(text: &str) -> Option<(Vec2, f32)> {
+    let mut numbers = text
+        .split_whitespace()
+        .map(|token| token.parse::<f32>().ok());
+
+    let target = vec2(numbers.next()??, numbers.next()??);
+    let screen_height = numbers.next()??;
+
+    if numbers.next().is_some() || !target.is_finite() || !screen_height.is_finite() {
+        return None;
+    }
+
+    Some((target, screen_height.clamp(10.0, 100.0)))
+}
+
+/// Reads the scene from [`SCENE_PATH`], falling back to [`assemble_simulation`] if it doesn't
+/// exist or can't be read
+pub fn load_scene() -> Simulation {
+    let scene = match scene_file::read(SCENE_PATH) {
+        Ok(scene) => scene,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return assemble_simulation(),
+        Err(error) => {
+            eprintln!("Failed to load {SCENE_PATH}: {error}");
+            return assemble_simulation();
+        }
     };
 
-    let corner_spring = LinearSpring {
-        target_distance: SQRT_2 / 3.0,
-        damping: 100.0,
-        tension: false,
-        ..Default::default()
+    let text = scene.text.trim_start();
+
+    let mut simulation = if text.starts_with(scenario::HEADER) {
+        let Some(scenario) = Scenario::load(text) else {
+            eprintln!("Failed to load {SCENE_PATH}: the scenario is malformed");
+            return assemble_simulation();
+        };
+
+        let mut simulation = Simulation::new();
+        scenario.start(&mut simulation, Vec2::ZERO);
+
+        simulation
+    } else if text.is_empty() {
+        // Only made of includes
+        Simulation::new()
+    } else {
+        match ship_clipboard::load(text) {
+            Some(simulation) => simulation,
+            None => {
+                eprintln!("Failed to load {SCENE_PATH}: it doesn't contain a ship");
+                return assemble_simulation();
+            }
+        }
     };
 
+    simulation.triggers = default_triggers();
+    scene.import_includes(&mut simulation);
+
+    simulation
+}
+
+pub fn assemble_simulation() -> Simulation {
+    let mut simulation = Simulation::new();
+
+    simulation
+        .soft_bodies
+        .insert(prefabs::habitat_bubble(vec2(-5.0, 0.0)).build());
+
+    // The cells with actors are given ids so that the actor config can find them again
     for x in 0..8 {
         for y in 0..6 {
             simulation.soft_bodies.insert(
-                SoftBodyBuilder::default()
-                    .offset(x as f32, -(y as f32) * 2.0)
-                    .point(0.0, 0.0)
-                    .with_attatchment_point(2)
-                    .with_internal_spring_start(0)
-                    .with_internal_spring_start(4)
-                    .point(1.0 / 3.0, 0.0)
-                    .with_internal_spring_start(1)
-                    .with_internal_spring_start(5)
-                    .with_attatchment_point(4)
-                    .point(1.0 / 3.0, 1.0 / 3.0)
-                    .with_internal_spring_start(2)
-                    .with_internal_spring_end(4, corner_spring)
-                    .point(1.0 / 3.0, 2.0 / 3.0)
-                    .with_internal_spring_start(3)
-                    .with_internal_spring_start(6)
-                    .point(1.0 / 3.0, 1.0)
-                    .with_attatchment_point(2)
-                    .with_internal_spring_start(7)
-                    .with_internal_spring_end(0, slight_diagonal_spring)
-                    .point(0.0, 1.0)
-                    .with_internal_spring_end(1, slight_diagonal_spring)
-                    .with_internal_spring_end(6, corner_spring)
-                    .point(0.0, 2.0 / 3.0)
-                    .with_internal_spring_end(3, orthogonal_spring)
-                    .with_internal_spring_end(7, corner_spring)
-                    .with_actor(Actor::RocketMotor {
-                        line: 0,
-                        force: vec2(100.0, 0.0),
-                        enable: Keybind {
-                            activate: vec![KeyCode::W],
-                            disable: vec![KeyCode::S],
-                        },
-                        particle_time: 0.0,
-                        max_particle_time: 0.005,
-                    })
-                    .point(0.0, 1.0 / 3.0)
-                    .with_internal_spring_end(2, orthogonal_spring)
-                    .with_internal_spring_end(5, corner_spring)
+                prefabs::thruster_cell(vec2(x as f32, -(y as f32) * 2.0))
+                    .id(x * 6 + y)
                     .build(),
             );
         }
@@ -230,41 +427,8 @@ fn assemble_simulation() -> Simulation {
     for x in 0..8 {
         for y in 0..6 {
             simulation.soft_bodies.insert(
-                SoftBodyBuilder::default()
-                    .gas_force(40.0)
-                    .offset(-(x as f32) - 10.0, -(y as f32) * 2.0)
-                    .base_angular_spring(Some(AngularSpring {
-                        damping: 20.0,
-                        ..Default::default()
-                    }))
-                    .with_actor(Actor::Piston {
-                        lengths: vec![
-                            (SpringIndex::Edge(0), 1.0 / 3.0, 1.0),
-                            (SpringIndex::Edge(4), 1.0 / 3.0, 1.0),
-                            (SpringIndex::Internal(0), 1.0 / 3.0, 1.0),
-                            (SpringIndex::Internal(1), 1.0 / 3.0, 1.0),
-                        ],
-                        enable: Keybind {
-                            activate: vec![KeyCode::Enter],
-                            disable: vec![],
-                        },
-                    })
-                    .point(0.0, 0.0)
-                    .with_spring(piston_spring)
-                    .point(1.0 / 3.0, 0.0)
-                    .with_attatchment_point(4)
-                    .point(1.0 / 3.0, 1.0 / 3.0)
-                    .with_internal_spring_start(2)
-                    .point(1.0 / 3.0, 2.0 / 3.0)
-                    .with_internal_spring_start(3)
-                    .point(1.0 / 3.0, 1.0)
-                    .with_spring(piston_spring)
-                    .point(0.0, 1.0)
-                    .with_attatchment_point(4)
-                    .point(0.0, 2.0 / 3.0)
-                    .with_internal_spring_end(3, piston_spring)
-                    .point(0.0, 1.0 / 3.0)
-                    .with_internal_spring_end(2, piston_spring)
+                prefabs::piston_cell(vec2(-(x as f32) - 10.0, -(y as f32) * 2.0))
+                    .id(48 + x * 6 + y)
                     .build(),
             );
         }
@@ -272,65 +436,13 @@ fn assemble_simulation() -> Simulation {
 
     for x in 0..12 {
         for y in 2..12 {
-            simulation.soft_bodies.insert(
-                SoftBodyBuilder::default()
-                    .offset(x as f32 * 4.0, y as f32 * 2.0)
-                    .point(0.0, 0.0)
-                    .with_attatchment_point(4)
-                    .with_internal_spring_start(0)
-                    .point(1.0 / 3.0, 0.0)
-                    .with_internal_spring_start(2)
-                    .point(2.0 / 3.0, 0.0)
-                    .with_internal_spring_start(3)
-                    .point(1.0, 0.0)
-                    .with_attatchment_point(4)
-                    .with_internal_spring_start(1)
-                    .point(1.0, 1.0 / 3.0)
-                    .with_internal_spring_end(3, corner_spring)
-                    .point(1.0, 2.0 / 3.0)
-                    .with_internal_spring_start(4)
-                    .point(1.0, 1.0)
-                    .with_attatchment_point(4)
-                    .with_internal_spring_end(0, diagonal_spring)
-                    .point(2.0 / 3.0, 1.0)
-                    .with_internal_spring_end(4, corner_spring)
-                    .point(1.0 / 3.0, 1.0)
-                    .with_internal_spring_start(5)
-                    .point(0.0, 1.0)
-                    .with_attatchment_point(4)
-                    .with_internal_spring_end(1, diagonal_spring)
-                    .point(0.0, 2.0 / 3.0)
-                    .with_internal_spring_end(5, corner_spring)
-                    .point(0.0, 1.0 / 3.0)
-                    .with_internal_spring_end(2, corner_spring)
-                    .build(),
-            );
+            simulation
+                .soft_bodies
+                .insert(prefabs::hull_block(vec2(x as f32 * 4.0, y as f32 * 2.0)).build());
 
-            simulation.soft_bodies.insert(
-                SoftBodyBuilder::default()
-                    .offset(x as f32 * 4.0 - 1.0, y as f32 * 2.0)
-                    .point(0.0, 0.0)
-                    .with_attatchment_point(2)
-                    .with_internal_spring_start(0)
-                    .point(1.0 / 3.0, 0.0)
-                    .with_internal_spring_start(1)
-                    .with_attatchment_point(4)
-                    .point(1.0 / 3.0, 1.0 / 3.0)
-                    .with_internal_spring_start(2)
-                    .point(1.0 / 3.0, 2.0 / 3.0)
-                    .with_internal_spring_start(3)
-                    .point(1.0 / 3.0, 1.0)
-                    .with_attatchment_point(2)
-                    .with_internal_spring_end(0, slight_diagonal_spring)
-                    .point(0.0, 1.0)
-                    .with_attatchment_point(4)
-                    .with_internal_spring_end(1, slight_diagonal_spring)
-                    .point(0.0, 2.0 / 3.0)
-                    .with_internal_spring_end(3, orthogonal_spring)
-                    .point(0.0, 1.0 / 3.0)
-                    .with_internal_spring_end(2, orthogonal_spring)
-                    .build(),
-            );
+            simulation
+                .soft_bodies
+                .insert(prefabs::connector(vec2(x as f32 * 4.0 - 1.0, y as f32 * 2.0)).build());
         }
     }
 
@@ -338,37 +450,9 @@ fn assemble_simulation() -> Simulation {
 
     for x in 0..12 {
         for y in 2..12 {
-            let mut builder = SoftBodyBuilder::default()
-                .gas_force(10.0)
-                .friction(1.0)
-                .mass(0.5)
-                .base_angular_spring(Some(AngularSpring {
-                    force_constant: 50.0,
-                    damping: 5.0,
-                    outwards: false,
-                    ..Default::default()
-                }))
-                .base_spring({
-                    LinearSpring {
-                        force_constant: 50.0,
-                        maximum_force: 1.5,
-                        ..Default::default()
-                    }
-                })
-                .spring_scale(0.5)
-                .offset(x as f32 * 4.0 + 2.0, y as f32 * 2.0);
-
-            for i in 0..12 {
-                let angle = (i as f32 + 0.5) / 12.0 * TAU;
-
-                builder = builder.point(angle.cos(), angle.sin());
-
-                if i % 3 == 1 {
-                    builder = builder.with_attatchment_point(4);
-                }
-            }
-
-            let key = simulation.soft_bodies.insert(builder.build());
+            let key = simulation
+                .soft_bodies
+                .insert(prefabs::balloon(vec2(x as f32 * 4.0 + 2.0, y as f32 * 2.0)).build());
 
             keys.push(key);
         }
@@ -387,36 +471,20 @@ fn assemble_simulation() -> Simulation {
         ])
         .unwrap();
 
+    simulation.triggers = default_triggers();
+
     simulation.update_keys();
 
     simulation
 }
 
-pub fn habitat_bubble(offset: Vec2) -> SoftBody {
-    let mut builder = SoftBodyBuilder::default()
-        .connection_state(ConnectionState::Source)
-        .gas_force(10.0)
-        .mass(0.5)
-        .base_angular_spring(Some(AngularSpring {
-            force_constant: 50.0,
-            damping: 5.0,
-            ..Default::default()
-        }))
-        .spring_scale(0.75)
-        .with_actor(Actor::HabitatBubble {
-            minimum_pressure: 0.5,
-        })
-        .offset_ex(offset);
-
-    for i in 0..12 {
-        let angle = (i as f32 + 0.5) / 12.0 * TAU;
-
-        builder = builder.point(angle.cos(), angle.sin());
-
-        if i % 3 == 1 {
-            builder = builder.with_attatchment_point(4);
-        }
-    }
-
-    builder.build()
+/// The triggers of [`assemble_simulation`], which loaded scenes get too
+pub fn default_triggers() -> Vec<simulation::Trigger> {
+    vec![simulation::Trigger::refuel_zone(
+        BoundingBox {
+            min_corner: vec2(-8.0, -3.0),
+            size: vec2(6.0, 6.0),
+        },
+        20.0,
+    )]
 }
@@ -4,7 +4,9 @@ use macroquad::{
     shapes::{self, DrawRectangleParams},
 };
 
-use crate::utils;
+use nalgebra::Vector2;
+
+use crate::{stars::AddOffset, utils};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Particle {
@@ -62,6 +64,12 @@ pub enum Shape {
     Rectangle { aspect: f32 },
 }
 
+impl AddOffset for Particle {
+    fn add_offset(&mut self, offset: Vector2<f32>) {
+        self.position += vec2(offset.x, offset.y);
+    }
+}
+
 impl Shape {
     pub fn draw(&self, position: Vec2, rotation: f32, size: f32, color: Color) {
         match self {
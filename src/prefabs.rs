@@ -0,0 +1,307 @@
+use std::f32::consts::{SQRT_2, TAU};
+
+use macroquad::{
+    input::KeyCode,
+    math::{Vec2, vec2},
+};
+
+use crate::soft_body::{
+    Actor, AngularSpring, ConnectionState, Keybind, LinearSpring, SoftBodyBuilder, SpringIndex,
+};
+
+/// Starts building a soft body at an offset
+pub type Prefab = fn(Vec2) -> SoftBodyBuilder;
+
+/// Every prefab which can be spawned from the palette, with its name
+pub const PALETTE: [(&str, Prefab); 8] = [
+    ("Thruster cell", thruster_cell),
+    ("Hull block", hull_block),
+    ("Connector", connector),
+    ("Balloon", balloon),
+    ("Habitat bubble", habitat_bubble),
+    ("Piston", piston_cell),
+    ("Reaction wheel", reaction_wheel),
+    ("Asteroid", asteroid),
+];
+
+/// The seed of the [`asteroid`] in the palette, so the preview keeps the same shape as it follows
+/// the mouse
+pub const ASTEROID_SEED: u64 = 2;
+
+fn diagonal_spring() -> LinearSpring {
+    LinearSpring {
+        target_distance: SQRT_2,
+        ..Default::default()
+    }
+}
+
+fn slight_diagonal_spring() -> LinearSpring {
+    LinearSpring {
+        target_distance: vec2(1.0, 1.0 / 3.0).length(),
+        ..Default::default()
+    }
+}
+
+fn orthogonal_spring() -> LinearSpring {
+    LinearSpring {
+        target_distance: 1.0 / 3.0,
+        ..Default::default()
+    }
+}
+
+fn piston_spring() -> LinearSpring {
+    LinearSpring {
+        target_distance: 1.0 / 3.0,
+        force_constant: 500.0,
+        damping: 100.0,
+        maximum_force: 125.0,
+        destroy_on_maximum: false,
+        ..Default::default()
+    }
+}
+
+fn corner_spring() -> LinearSpring {
+    LinearSpring {
+        target_distance: SQRT_2 / 3.0,
+        damping: 100.0,
+        tension: false,
+        ..Default::default()
+    }
+}
+
+/// A 1/3 by 1 cell with a rocket motor on its left edge, controlled by W and S
+pub fn thruster_cell(offset: Vec2) -> SoftBodyBuilder {
+    SoftBodyBuilder::default()
+        .offset_ex(offset)
+        .point(0.0, 0.0)
+        .with_attatchment_point(2)
+        .with_internal_spring_start(0)
+        .with_internal_spring_start(4)
+        .point(1.0 / 3.0, 0.0)
+        .with_internal_spring_start(1)
+        .with_internal_spring_start(5)
+        .with_attatchment_point(4)
+        .point(1.0 / 3.0, 1.0 / 3.0)
+        .with_internal_spring_start(2)
+        .with_internal_spring_end(4, corner_spring())
+        .point(1.0 / 3.0, 2.0 / 3.0)
+        .with_internal_spring_start(3)
+        .with_internal_spring_start(6)
+        .point(1.0 / 3.0, 1.0)
+        .with_attatchment_point(2)
+        .with_internal_spring_start(7)
+        .with_internal_spring_end(0, slight_diagonal_spring())
+        .point(0.0, 1.0)
+        .with_internal_spring_end(1, slight_diagonal_spring())
+        .with_internal_spring_end(6, corner_spring())
+        .point(0.0, 2.0 / 3.0)
+        .with_internal_spring_end(3, orthogonal_spring())
+        .with_internal_spring_end(7, corner_spring())
+        .with_actor(Actor::RocketMotor {
+            line: 0,
+            force: vec2(100.0, 0.0),
+            enable: Keybind {
+                activate: vec![KeyCode::W],
+                disable: vec![KeyCode::S],
+                forced: None,
+            },
+            particle_time: 0.0,
+            max_particle_time: 0.005,
+            gimbal: None,
+            exhaust_start: Actor::EXHAUST_START_COLOR,
+            exhaust_end: Actor::EXHAUST_END_COLOR,
+        })
+        .point(0.0, 1.0 / 3.0)
+        .with_internal_spring_end(2, orthogonal_spring())
+        .with_internal_spring_end(5, corner_spring())
+}
+
+/// A 1/3 by 1 cell which extends to 1 by 1 while Enter is held
+pub fn piston_cell(offset: Vec2) -> SoftBodyBuilder {
+    SoftBodyBuilder::default()
+        .gas_force(40.0)
+        .offset_ex(offset)
+        .base_angular_spring(Some(AngularSpring {
+            damping: 20.0,
+            ..Default::default()
+        }))
+        .with_actor(Actor::Piston {
+            lengths: vec![
+                (SpringIndex::Edge(0), 1.0 / 3.0, 1.0),
+                (SpringIndex::Edge(4), 1.0 / 3.0, 1.0),
+                (SpringIndex::Internal(0), 1.0 / 3.0, 1.0),
+                (SpringIndex::Internal(1), 1.0 / 3.0, 1.0),
+            ],
+            enable: Keybind {
+                activate: vec![KeyCode::Enter],
+                disable: vec![],
+                forced: None,
+            },
+        })
+        .point(0.0, 0.0)
+        .with_spring(piston_spring())
+        .point(1.0 / 3.0, 0.0)
+        .with_attatchment_point(4)
+        .point(1.0 / 3.0, 1.0 / 3.0)
+        .with_internal_spring_start(2)
+        .point(1.0 / 3.0, 2.0 / 3.0)
+        .with_internal_spring_start(3)
+        .point(1.0 / 3.0, 1.0)
+        .with_spring(piston_spring())
+        .point(0.0, 1.0)
+        .with_attatchment_point(4)
+        .point(0.0, 2.0 / 3.0)
+        .with_internal_spring_end(3, piston_spring())
+        .point(0.0, 1.0 / 3.0)
+        .with_internal_spring_end(2, piston_spring())
+}
+
+/// A 1 by 1 block with a seam on each side
+pub fn hull_block(offset: Vec2) -> SoftBodyBuilder {
+    SoftBodyBuilder::default()
+        .offset_ex(offset)
+        .point(0.0, 0.0)
+        .with_attatchment_point(4)
+        .with_internal_spring_start(0)
+        .point(1.0 / 3.0, 0.0)
+        .with_internal_spring_start(2)
+        .point(2.0 / 3.0, 0.0)
+        .with_internal_spring_start(3)
+        .point(1.0, 0.0)
+        .with_attatchment_point(4)
+        .with_internal_spring_start(1)
+        .point(1.0, 1.0 / 3.0)
+        .with_internal_spring_end(3, corner_spring())
+        .point(1.0, 2.0 / 3.0)
+        .with_internal_spring_start(4)
+        .point(1.0, 1.0)
+        .with_attatchment_point(4)
+        .with_internal_spring_end(0, diagonal_spring())
+        .point(2.0 / 3.0, 1.0)
+        .with_internal_spring_end(4, corner_spring())
+        .point(1.0 / 3.0, 1.0)
+        .with_internal_spring_start(5)
+        .point(0.0, 1.0)
+        .with_attatchment_point(4)
+        .with_internal_spring_end(1, diagonal_spring())
+        .point(0.0, 2.0 / 3.0)
+        .with_internal_spring_end(5, corner_spring())
+        .point(0.0, 1.0 / 3.0)
+        .with_internal_spring_end(2, corner_spring())
+}
+
+/// A [`hull_block`] which spins clockwise while E is held and counterclockwise while Q is held
+pub fn reaction_wheel(offset: Vec2) -> SoftBodyBuilder {
+    hull_block(offset).with_actor(Actor::ReactionWheel {
+        torque: 20.0,
+        enable_cw: Keybind {
+            activate: vec![KeyCode::E],
+            disable: vec![],
+            forced: None,
+        },
+        enable_ccw: Keybind {
+            activate: vec![KeyCode::Q],
+            disable: vec![],
+            forced: None,
+        },
+    })
+}
+
+/// A 1/3 by 1 strip with a short seam on each end and a long seam on each side
+pub fn connector(offset: Vec2) -> SoftBodyBuilder {
+    SoftBodyBuilder::default()
+        .offset_ex(offset)
+        .point(0.0, 0.0)
+        .with_attatchment_point(2)
+        .with_internal_spring_start(0)
+        .point(1.0 / 3.0, 0.0)
+        .with_internal_spring_start(1)
+        .with_attatchment_point(4)
+        .point(1.0 / 3.0, 1.0 / 3.0)
+        .with_internal_spring_start(2)
+        .point(1.0 / 3.0, 2.0 / 3.0)
+        .with_internal_spring_start(3)
+        .point(1.0 / 3.0, 1.0)
+        .with_attatchment_point(2)
+        .with_internal_spring_end(0, slight_diagonal_spring())
+        .point(0.0, 1.0)
+        .with_attatchment_point(4)
+        .with_internal_spring_end(1, slight_diagonal_spring())
+        .point(0.0, 2.0 / 3.0)
+        .with_internal_spring_end(3, orthogonal_spring())
+        .point(0.0, 1.0 / 3.0)
+        .with_internal_spring_end(2, orthogonal_spring())
+}
+
+/// A circle which is held open by its gas, since its springs are half the length of its edges
+pub fn balloon(offset: Vec2) -> SoftBodyBuilder {
+    let mut builder = SoftBodyBuilder::default()
+        .gas_force(10.0)
+        .friction(1.0)
+        .mass(0.5)
+        .base_angular_spring(Some(AngularSpring {
+            force_constant: 50.0,
+            damping: 5.0,
+            outwards: false,
+            ..Default::default()
+        }))
+        .base_spring({
+            LinearSpring {
+                force_constant: 50.0,
+                maximum_force: 75.0,
+                ..Default::default()
+            }
+        })
+        .spring_scale(0.5)
+        .offset_ex(offset);
+
+    for i in 0..12 {
+        let angle = (i as f32 + 0.5) / 12.0 * TAU;
+
+        builder = builder.point(angle.cos(), angle.sin());
+
+        if i % 3 == 1 {
+            builder = builder.with_attatchment_point(4);
+        }
+    }
+
+    builder
+}
+
+/// Like a [`balloon`], but keeps the camera on it while it is pressurized, and powers the actors
+/// connected to it
+pub fn habitat_bubble(offset: Vec2) -> SoftBodyBuilder {
+    let mut builder = SoftBodyBuilder::default()
+        .connection_state(ConnectionState::Source)
+        .gas_force(10.0)
+        .mass(0.5)
+        .base_angular_spring(Some(AngularSpring {
+            force_constant: 50.0,
+            damping: 5.0,
+            ..Default::default()
+        }))
+        .spring_scale(0.75)
+        .with_actor(Actor::HabitatBubble {
+            minimum_pressure: 0.5,
+            fuel: 100.0,
+            fuel_capacity: 100.0,
+        })
+        .offset_ex(offset);
+
+    for i in 0..12 {
+        let angle = (i as f32 + 0.5) / 12.0 * TAU;
+
+        builder = builder.point(angle.cos(), angle.sin());
+
+        if i % 3 == 1 {
+            builder = builder.with_attatchment_point(4);
+        }
+    }
+
+    builder
+}
+
+/// A lumpy rock with a radius of about 1.5, to bump into. See [`SoftBodyBuilder::asteroid`]
+pub fn asteroid(offset: Vec2) -> SoftBodyBuilder {
+    SoftBodyBuilder::asteroid(1.5, 0.3, 18, ASTEROID_SEED).offset_ex(offset)
+}
@@ -0,0 +1,175 @@
+use std::str::SplitWhitespace;
+
+use macroquad::math::{Vec2, vec2};
+
+use crate::{
+    ship_clipboard,
+    simulation::{Simulation, SoftBodyKey, Trigger},
+    soft_body::BoundingBox,
+};
+
+/// The first token of every scenario, so that plain ships are told apart from scenarios
+pub const HEADER: &str = "jello_space_pond_scenario_1";
+
+/// A starting scene and the goal of a challenge. Written as a line with [`HEADER`] and the win
+/// condition, followed by the scene as a ship from [`ship_clipboard::save`].
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    pub simulation: Simulation,
+    pub win_condition: WinCondition,
+}
+
+/// What has to happen for the objective of a [`Scenario`] to be complete. Checked every tick by
+/// [`Simulation::update_objective`].
+#[derive(Clone, Copy, Debug)]
+pub enum WinCondition {
+    /// `soft_body` has to be inside `area`, the same way it would be inside a [`Trigger`]
+    BodyInZone {
+        soft_body: SoftBodyKey,
+        area: BoundingBox,
+    },
+    /// `a` and `b` have to be part of the same ship
+    BodiesConnected { a: SoftBodyKey, b: SoftBodyKey },
+}
+
+impl WinCondition {
+    pub fn is_met(&self, simulation: &Simulation) -> bool {
+        match *self {
+            WinCondition::BodyInZone { soft_body, area } => simulation
+                .soft_bodies
+                .get(soft_body)
+                .is_some_and(|soft_body| Trigger::area_contains(&area, soft_body)),
+            WinCondition::BodiesConnected { a, b } => {
+                simulation.soft_bodies.contains_key(a)
+                    && simulation.soft_bodies.contains_key(b)
+                    && simulation.connected_component(a).contains(&b)
+            }
+        }
+    }
+
+    /// Describes what has to be done, for the objective shown on screen
+    pub fn description(&self) -> &'static str {
+        match self {
+            WinCondition::BodyInZone { .. } => "Move the marked body into the marked zone.",
+            WinCondition::BodiesConnected { .. } => "Connect the two marked bodies.",
+        }
+    }
+
+    /// The soft bodies the condition is about
+    pub fn soft_bodies(&self) -> Vec<SoftBodyKey> {
+        match *self {
+            WinCondition::BodyInZone { soft_body, .. } => vec![soft_body],
+            WinCondition::BodiesConnected { a, b } => vec![a, b],
+        }
+    }
+}
+
+impl Scenario {
+    /// Reads a scenario written by [`Self::save`]. The soft bodies in the win condition are
+    /// indices into the ship, in the order it was saved in. Returns `None` if the text is
+    /// malformed or refers to soft bodies that don't exist.
+    pub fn load(text: &str) -> Option<Self> {
+        let (first_line, ship) = text.split_once('\n')?;
+        let mut tokens = first_line.split_whitespace();
+
+        if tokens.next()? != HEADER {
+            return None;
+        }
+
+        let simulation = ship_clipboard::load(ship)?;
+
+        let soft_body = |tokens: &mut SplitWhitespace| {
+            let index = tokens.next()?.parse::<usize>().ok()?;
+            simulation.keys.get(index).copied()
+        };
+        let number = |tokens: &mut SplitWhitespace| tokens.next()?.parse::<f32>().ok();
+
+        let win_condition = match tokens.next()? {
+            "zone" => WinCondition::BodyInZone {
+                soft_body: soft_body(&mut tokens)?,
+                area: BoundingBox {
+                    min_corner: vec2(number(&mut tokens)?, number(&mut tokens)?),
+                    size: vec2(number(&mut tokens)?, number(&mut tokens)?),
+                },
+            },
+            "connected" => WinCondition::BodiesConnected {
+                a: soft_body(&mut tokens)?,
+                b: soft_body(&mut tokens)?,
+            },
+            _ => return None,
+        };
+
+        if tokens.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            simulation,
+            win_condition,
+        })
+    }
+
+    /// Writes every soft body of `simulation` and `win_condition`, with positions relative to
+    /// their center of mass like [`ship_clipboard::save`]. Returns `None` if the win condition
+    /// refers to a soft body which isn't in the simulation.
+    pub fn save(simulation: &Simulation, win_condition: WinCondition) -> Option<String> {
+        let order = ship_clipboard::save_order(simulation, &simulation.keys);
+        let index = |key: SoftBodyKey| order.iter().position(|&other| other == key);
+
+        let (total_mass, mass_moment) = simulation.soft_bodies.values().fold(
+            (0.0, Vec2::ZERO),
+            |(total_mass, mass_moment), soft_body| {
+                (
+                    total_mass + soft_body.total_mass(),
+                    mass_moment + soft_body.center_of_mass() * soft_body.total_mass(),
+                )
+            },
+        );
+        let center = mass_moment / total_mass.max(f32::EPSILON);
+
+        let condition = match win_condition {
+            WinCondition::BodyInZone { soft_body, area } => {
+                let min_corner = area.min_corner - center;
+
+                format!(
+                    "zone {} {} {} {} {}",
+                    index(soft_body)?,
+                    min_corner.x,
+                    min_corner.y,
+                    area.size.x,
+                    area.size.y,
+                )
+            }
+            WinCondition::BodiesConnected { a, b } => {
+                format!("connected {} {}", index(a)?, index(b)?)
+            }
+        };
+
+        Some(format!(
+            "{HEADER} {condition}\n{}",
+            ship_clipboard::save(simulation, &simulation.keys),
+        ))
+    }
+
+    /// Replaces the scene of `simulation` with this scenario, placed so its center of mass is on
+    /// `position`
+    pub fn start(self, simulation: &mut Simulation, position: Vec2) {
+        simulation.clear();
+
+        let keys = simulation.import(self.simulation, position);
+
+        simulation.win_condition = Some(match self.win_condition {
+            WinCondition::BodyInZone { soft_body, area } => WinCondition::BodyInZone {
+                soft_body: keys[&soft_body],
+                area: BoundingBox {
+                    min_corner: area.min_corner + position,
+                    ..area
+                },
+            },
+            WinCondition::BodiesConnected { a, b } => WinCondition::BodiesConnected {
+                a: keys[&a],
+                b: keys[&b],
+            },
+        });
+    }
+}
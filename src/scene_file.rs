@@ -0,0 +1,213 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use macroquad::math::{Vec2, vec2};
+
+use crate::{ship_clipboard, simulation::Simulation};
+
+/// A hand written scene file with its comments removed and its includes read. Everything after
+/// `//` on a line is a comment. A line like `include "other.txt" 5 -2` adds the ship in
+/// `other.txt`, found next to the file including it, centered on the position after it, or on the
+/// origin if it is left out. Included files can have comments and includes of their own.
+#[derive(Clone, Debug)]
+pub struct SceneFile {
+    /// Every line which isn't a comment or an include, which should be a ship or a [`Scenario`]
+    ///
+    /// [`Scenario`]: crate::scenario::Scenario
+    pub text: String,
+    /// The ships of the included files and where to center them
+    pub includes: Vec<(Simulation, Vec2)>,
+}
+
+impl SceneFile {
+    /// Adds the ships of [`Self::includes`] to `simulation`
+    pub fn import_includes(self, simulation: &mut Simulation) {
+        for (ship, position) in self.includes {
+            simulation.import(ship, position);
+        }
+    }
+}
+
+/// Reads the scene file at `path` and every file it includes. A missing file at `path` gives an
+/// error of kind [`io::ErrorKind::NotFound`], but every problem with an include, including a
+/// missing file or a file which includes itself, gives an error of kind
+/// [`io::ErrorKind::InvalidData`] which says where the include is.
+pub fn read(path: impl AsRef<Path>) -> io::Result<SceneFile> {
+    read_including(path.as_ref(), &mut Vec::new())
+}
+
+/// `including` is the chain of files being read, so cycles can be detected
+fn read_including(path: &Path, including: &mut Vec<PathBuf>) -> io::Result<SceneFile> {
+    let text = fs::read_to_string(path)?;
+    let canonical_path = fs::canonicalize(path)?;
+
+    if including.contains(&canonical_path) {
+        return Err(invalid_data(format!("{} includes itself", path.display())));
+    }
+
+    including.push(canonical_path);
+
+    let mut scene = SceneFile {
+        text: String::new(),
+        includes: Vec::new(),
+    };
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split_once("//").map_or(line, |(line, _)| line);
+
+        let Some(arguments) = line
+            .trim_start()
+            .strip_prefix("include")
+            .filter(|arguments| arguments.starts_with(|c: char| c.is_whitespace() || c == '"'))
+        else {
+            scene.text.push_str(line);
+            scene.text.push('\n');
+            continue;
+        };
+
+        let location = format!("{}:{}", path.display(), number + 1);
+
+        let (file, position) = parse_include(arguments)
+            .ok_or_else(|| invalid_data(format!("{location}: malformed include")))?;
+        let file = path.parent().unwrap_or(Path::new("")).join(file);
+
+        let included = read_including(&file, including)
+            .map_err(|error| invalid_data(format!("{location}: {error}")))?;
+
+        let mut ship = if included.text.trim().is_empty() {
+            // Only made of includes
+            Simulation::new()
+        } else {
+            ship_clipboard::load(&included.text).ok_or_else(|| {
+                invalid_data(format!(
+                    "{location}: {} doesn't contain a ship",
+                    file.display()
+                ))
+            })?
+        };
+        included.import_includes(&mut ship);
+
+        scene.includes.push((ship, position));
+    }
+
+    including.pop();
+
+    Ok(scene)
+}
+
+/// Reads `"file" x y` or `"file"`
+fn parse_include(arguments: &str) -> Option<(&str, Vec2)> {
+    let (file, rest) = arguments.trim().strip_prefix('"')?.split_once('"')?;
+
+    let numbers = rest
+        .split_whitespace()
+        .map(|token| token.parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match numbers[..] {
+        [] => Some((file, Vec2::ZERO)),
+        [x, y] if x.is_finite() && y.is_finite() => Some((file, vec2(x, y))),
+        _ => None,
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A new empty directory for the files of one test
+    fn test_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "jello_space_pond_scene_file_{name}_{}",
+            std::process::id(),
+        ));
+
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        directory
+    }
+
+    #[test]
+    fn parses_includes_with_and_without_positions() {
+        assert_eq!(
+            parse_include(r#" "ship.txt" 5 -2"#),
+            Some(("ship.txt", vec2(5.0, -2.0))),
+        );
+        assert_eq!(
+            parse_include(r#""ship.txt""#),
+            Some(("ship.txt", Vec2::ZERO))
+        );
+        assert_eq!(parse_include(r#""ship.txt" 5"#), None);
+        assert_eq!(parse_include(r#""ship.txt" 5 nan"#), None);
+        assert_eq!(parse_include("ship.txt"), None);
+    }
+
+    #[test]
+    fn strips_comments() {
+        let directory = test_directory("comments");
+        let path = directory.join("scene.txt");
+        fs::write(&path, "first // a comment\n// a whole line\nsecond\n").unwrap();
+
+        let scene = read(&path).unwrap();
+
+        assert_eq!(
+            scene.text.split_whitespace().collect::<Vec<_>>(),
+            ["first", "second"]
+        );
+        assert!(scene.includes.is_empty());
+    }
+
+    #[test]
+    fn only_matches_the_include_keyword() {
+        let directory = test_directory("keyword");
+        let path = directory.join("scene.txt");
+        fs::write(&path, "included_ship 1 2\n").unwrap();
+
+        let scene = read(&path).unwrap();
+
+        assert_eq!(scene.text.trim(), "included_ship 1 2");
+        assert!(scene.includes.is_empty());
+    }
+
+    #[test]
+    fn includes_files_made_only_of_includes() {
+        let directory = test_directory("nested");
+        fs::write(directory.join("scene.txt"), "include \"middle.txt\" 1 2\n").unwrap();
+        fs::write(directory.join("middle.txt"), "include \"empty.txt\"\n").unwrap();
+        fs::write(directory.join("empty.txt"), "// nothing here\n").unwrap();
+
+        let scene = read(directory.join("scene.txt")).unwrap();
+
+        assert_eq!(scene.includes.len(), 1);
+        assert_eq!(scene.includes[0].1, vec2(1.0, 2.0));
+        assert!(scene.includes[0].0.soft_bodies.is_empty());
+    }
+
+    #[test]
+    fn rejects_files_which_include_themselves() {
+        let directory = test_directory("cycle");
+        fs::write(directory.join("a.txt"), "include \"b.txt\"\n").unwrap();
+        fs::write(directory.join("b.txt"), "include \"a.txt\"\n").unwrap();
+
+        let error = read(directory.join("a.txt")).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_missing_includes() {
+        let directory = test_directory("missing");
+        fs::write(directory.join("scene.txt"), "include \"missing.txt\"\n").unwrap();
+
+        let error = read(directory.join("scene.txt")).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,890 @@
+use std::{collections::HashMap, str::SplitWhitespace};
+
+use macroquad::{
+    color::Color,
+    input::KeyCode,
+    math::{Vec2, vec2},
+    miniquad::window,
+};
+
+use crate::{
+    actor_config,
+    constraint::{Constraint, PointHandle},
+    simulation::{Simulation, SoftBodyKey},
+    soft_body::{
+        Actor, AngularSpring, AttatchmentPoint, AttatchmentPointHandle, ConnectionState, Gimbal,
+        Keybind, Line, LinearSpring, Point, SoftBody, SpringIndex,
+    },
+};
+
+/// The first token of every copied ship, so that other text on the clipboard is rejected early
+const HEADER: &str = "jello_space_pond_ship_6";
+/// Ships copied before seams could be joined at junctions. Each seam has at most one connection,
+/// and each linear spring's `maximum_force` is how far it can be stretched rather than a force.
+const LEGACY_HEADER: &str = "jello_space_pond_ship_5";
+
+/// Puts the connected component of `key` on the system clipboard. Returns the number of soft
+/// bodies copied.
+pub fn copy(simulation: &Simulation, key: SoftBodyKey) -> usize {
+    let component = simulation.connected_component(key);
+
+    window::clipboard_set(&save(simulation, &component));
+
+    component.len()
+}
+
+/// Imports the ship on the system clipboard centered on `position`. Returns `None` if the
+/// clipboard doesn't contain a ship.
+pub fn paste(simulation: &mut Simulation, position: Vec2) -> Option<Vec<SoftBodyKey>> {
+    let ship = load(&window::clipboard_get()?)?;
+
+    Some(simulation.import(ship, position).into_values().collect())
+}
+
+/// The order [`save`] writes `soft_bodies` in: those with an [`SoftBody::id`] first, by id, then
+/// the rest by key. Keys aren't stable across loads, but the same scene loaded the same way gets
+/// the same keys, so saving it twice gives the same text no matter the order `soft_bodies` is in.
+///
+/// [`SoftBody::id`]: crate::soft_body::SoftBody::id
+pub fn save_order(simulation: &Simulation, soft_bodies: &[SoftBodyKey]) -> Vec<SoftBodyKey> {
+    let mut order = soft_bodies.to_vec();
+
+    order.sort_by_key(|&key| {
+        let id = simulation.soft_bodies[key].id;
+
+        (id.is_none(), id, key)
+    });
+
+    order
+}
+
+/// Writes `soft_bodies` and the constraints between them as whitespace separated tokens, with
+/// positions relative to their center of mass and velocities left out so they are pasted at rest.
+/// Soft bodies are written in [`save_order`], and constraints by the points they hold, so the text
+/// doesn't depend on the order of the slot maps.
+pub fn save(simulation: &Simulation, soft_bodies: &[SoftBodyKey]) -> String {
+    let soft_bodies = &save_order(simulation, soft_bodies);
+
+    let indices = soft_bodies
+        .iter()
+        .enumerate()
+        .map(|(i, &key)| (key, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut total_mass = 0.0;
+    let mut mass_moment = Vec2::ZERO;
+
+    for &key in soft_bodies {
+        for (point, _) in &simulation.soft_bodies[key].shape {
+            total_mass += point.mass;
+            mass_moment += point.position * point.mass;
+        }
+    }
+
+    let center = mass_moment / total_mass.max(f32::EPSILON);
+
+    let mut held_points = Vec::new();
+
+    for (constraint_key, constraint) in &simulation.constraints {
+        match constraint {
+            Constraint::HoldTogether { points, .. } => {
+                let Some(points) = points
+                    .iter()
+                    .map(|point| Some((*indices.get(&point.soft_body)?, point.index)))
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+
+                held_points.push((points, constraint_key));
+            }
+        }
+    }
+
+    held_points.sort();
+
+    let constraint_indices = held_points
+        .iter()
+        .enumerate()
+        .map(|(i, &(_, constraint_key))| (constraint_key, i))
+        .collect::<HashMap<_, _>>();
+    let constraints = held_points
+        .into_iter()
+        .map(|(points, _)| points)
+        .collect::<Vec<_>>();
+
+    let mut tokens = vec![HEADER.to_owned()];
+
+    soft_bodies.len().encode(&mut tokens);
+
+    for &key in soft_bodies {
+        let soft_body = &simulation.soft_bodies[key];
+
+        soft_body
+            .shape
+            .iter()
+            .map(|(point, line)| {
+                (
+                    Point {
+                        position: point.position - center,
+                        ..*point
+                    },
+                    *line,
+                    point
+                        .constraint
+                        .and_then(|key| constraint_indices.get(&key).copied()),
+                )
+            })
+            .collect::<Vec<_>>()
+            .encode(&mut tokens);
+        soft_body.internal_springs.encode(&mut tokens);
+        soft_body.gas_force.encode(&mut tokens);
+        soft_body
+            .attatchment_points
+            .iter()
+            .map(|attatchment_point| {
+                (
+                    attatchment_point.start_point,
+                    attatchment_point.length,
+                    attatchment_point
+                        .connections
+                        .iter()
+                        .filter_map(|connection| {
+                            Some((*indices.get(&connection.soft_body)?, connection.index))
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .encode(&mut tokens);
+        soft_body.actors.encode(&mut tokens);
+        soft_body.connection_state.encode(&mut tokens);
+        soft_body.collision_layer.encode(&mut tokens);
+        soft_body.collision_mask.encode(&mut tokens);
+        soft_body.health.encode(&mut tokens);
+        soft_body.frozen.encode(&mut tokens);
+        soft_body.id.encode(&mut tokens);
+    }
+
+    constraints.encode(&mut tokens);
+
+    tokens.join(" ")
+}
+
+/// Reads a ship written by [`save`] into a new simulation, ready to be imported. Returns `None` if
+/// the text is malformed, refers to points, springs, or soft bodies that don't exist, or connects
+/// a seam to its own soft body. Ships copied with [`LEGACY_HEADER`] are read as well.
+pub fn load(text: &str) -> Option<Simulation> {
+    let mut tokens = text.split_whitespace();
+
+    let legacy = match tokens.next()? {
+        HEADER => false,
+        LEGACY_HEADER => true,
+        _ => return None,
+    };
+
+    let mut simulation = Simulation::new();
+    let mut soft_bodies = Vec::new();
+
+    for _ in 0..usize::decode(&mut tokens)? {
+        let shape = Vec::<(Point, Line, Option<usize>)>::decode(&mut tokens)?;
+        let internal_springs = Vec::<([usize; 2], LinearSpring)>::decode(&mut tokens)?;
+        let gas_force = f32::decode(&mut tokens)?;
+        let attatchment_points = if legacy {
+            Vec::<(usize, usize, Option<(usize, usize)>)>::decode(&mut tokens)?
+                .into_iter()
+                .map(|(start_point, length, connection)| {
+                    (start_point, length, connection.into_iter().collect())
+                })
+                .collect()
+        } else {
+            Vec::<(usize, usize, Vec<(usize, usize)>)>::decode(&mut tokens)?
+        };
+        let actors = Vec::<Actor>::decode(&mut tokens)?;
+        let connection_state = ConnectionState::decode(&mut tokens)?;
+        let collision_layer = u32::decode(&mut tokens)?;
+        let collision_mask = u32::decode(&mut tokens)?;
+        let health = f32::decode(&mut tokens)?;
+        let frozen = bool::decode(&mut tokens)?;
+        let id = Option::<u64>::decode(&mut tokens)?;
+
+        let len = shape.len();
+
+        if len < 3
+            || internal_springs
+                .iter()
+                .any(|&([a, b], _)| a >= len || b >= len || a == b)
+            || attatchment_points
+                .iter()
+                .any(|(start_point, length, connections)| {
+                    *start_point >= len
+                        || *length == 0
+                        || *length > len
+                        || connections.len() > Simulation::MAX_SEAM_CONNECTIONS
+                })
+            || !actors
+                .iter()
+                .all(|actor| actor.is_valid(len, internal_springs.len()))
+        {
+            return None;
+        }
+
+        let constraints = shape
+            .iter()
+            .map(|&(_, _, constraint)| constraint)
+            .collect::<Vec<_>>();
+        let connections = attatchment_points
+            .iter()
+            .map(|(_, _, connections)| connections.clone())
+            .collect::<Vec<_>>();
+
+        let mut soft_body = SoftBody::new(
+            shape
+                .into_iter()
+                .map(|(point, line, _)| (point, line))
+                .collect(),
+            internal_springs,
+            gas_force,
+        );
+
+        if legacy {
+            for spring in soft_body.linear_springs_mut() {
+                spring.maximum_force *= spring.force_constant;
+            }
+        }
+
+        soft_body.attatchment_points = attatchment_points
+            .into_iter()
+            .map(|(start_point, length, _)| AttatchmentPoint {
+                start_point,
+                length,
+                connections: Vec::new(),
+            })
+            .collect();
+        soft_body.actors = actors;
+        soft_body.connection_state = connection_state;
+        soft_body.collision_layer = collision_layer;
+        soft_body.collision_mask = collision_mask;
+        soft_body.health = health;
+        soft_body.frozen = frozen;
+        soft_body.id = id;
+
+        soft_bodies.push((
+            simulation.soft_bodies.insert(soft_body),
+            constraints,
+            connections,
+        ));
+    }
+
+    let point_exists = |(soft_body, index): (usize, usize)| {
+        soft_bodies
+            .get(soft_body)
+            .is_some_and(|(key, _, _)| index < simulation.soft_bodies[*key].shape.len())
+    };
+    let attatchment_point_exists = |(soft_body, index): (usize, usize)| {
+        soft_bodies.get(soft_body).is_some_and(|(key, _, _)| {
+            index < simulation.soft_bodies[*key].attatchment_points.len()
+        })
+    };
+
+    let constraints = Vec::<Vec<(usize, usize)>>::decode(&mut tokens)?;
+
+    if tokens.next().is_some()
+        || !constraints
+            .iter()
+            .flatten()
+            .all(|&point| point_exists(point))
+        || !soft_bodies
+            .iter()
+            .enumerate()
+            .all(|(i, (_, point_constraints, connections))| {
+                point_constraints
+                    .iter()
+                    .flatten()
+                    .all(|&index| index < constraints.len())
+                    && connections.iter().flatten().all(|&connection| {
+                        connection.0 != i && attatchment_point_exists(connection)
+                    })
+            })
+    {
+        return None;
+    }
+
+    let constraint_keys = constraints
+        .into_iter()
+        .map(|points| {
+            simulation.constraints.insert(Constraint::HoldTogether {
+                points: points
+                    .into_iter()
+                    .map(|(soft_body, index)| PointHandle {
+                        soft_body: soft_bodies[soft_body].0,
+                        index,
+                    })
+                    .collect(),
+                last_correction: 0.0,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for (key, point_constraints, connections) in &soft_bodies {
+        let soft_body = &mut simulation.soft_bodies[*key];
+
+        for ((point, _), constraint) in soft_body.shape.iter_mut().zip(point_constraints) {
+            point.constraint = constraint.map(|index| constraint_keys[index]);
+        }
+
+        for (attatchment_point, connections) in
+            soft_body.attatchment_points.iter_mut().zip(connections)
+        {
+            attatchment_point.connections = connections
+                .iter()
+                .map(|&(soft_body, index)| AttatchmentPointHandle {
+                    soft_body: soft_bodies[soft_body].0,
+                    index,
+                })
+                .collect();
+        }
+    }
+
+    simulation.update_keys();
+
+    Some(simulation)
+}
+
+/// A value which can be written as whitespace separated tokens and read back
+trait Encode: Sized {
+    fn encode(&self, tokens: &mut Vec<String>);
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self>;
+}
+
+macro_rules! encode_with_display {
+    ($($type:ty),*) => {
+        $(
+            impl Encode for $type {
+                fn encode(&self, tokens: &mut Vec<String>) {
+                    tokens.push(self.to_string());
+                }
+
+                fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+                    tokens.next()?.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+encode_with_display!(f32, u32, u64, usize, bool);
+
+/// Written as the hex of its bytes after an `s`, so whitespace in it doesn't split it into tokens
+impl Encode for String {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        let mut token = "s".to_owned();
+
+        for byte in self.bytes() {
+            token.push_str(&format!("{byte:02x}"));
+        }
+
+        tokens.push(token);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        let hex = tokens.next()?.strip_prefix('s')?;
+
+        if hex.len() % 2 != 0 {
+            return None;
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        String::from_utf8(bytes).ok()
+    }
+}
+
+impl Encode for Vec2 {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.x.encode(tokens);
+        self.y.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(vec2(f32::decode(tokens)?, f32::decode(tokens)?))
+    }
+}
+
+impl Encode for Color {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        for channel in [self.r, self.g, self.b, self.a] {
+            channel.encode(tokens);
+        }
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(Color::new(
+            Encode::decode(tokens)?,
+            Encode::decode(tokens)?,
+            Encode::decode(tokens)?,
+            Encode::decode(tokens)?,
+        ))
+    }
+}
+
+impl Encode for KeyCode {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        tokens.push(format!("{self:?}"));
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        actor_config::parse_key_code(tokens.next()?)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        match self {
+            Some(value) => {
+                tokens.push("+".to_owned());
+                value.encode(tokens);
+            }
+            None => tokens.push("-".to_owned()),
+        }
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        match tokens.next()? {
+            "+" => Some(Some(T::decode(tokens)?)),
+            "-" => Some(None),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.len().encode(tokens);
+
+        for value in self {
+            value.encode(tokens);
+        }
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        (0..usize::decode(tokens)?)
+            .map(|_| T::decode(tokens))
+            .collect()
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for (A, B) {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.0.encode(tokens);
+        self.1.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some((A::decode(tokens)?, B::decode(tokens)?))
+    }
+}
+
+impl<A: Encode, B: Encode, C: Encode> Encode for (A, B, C) {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.0.encode(tokens);
+        self.1.encode(tokens);
+        self.2.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some((A::decode(tokens)?, B::decode(tokens)?, C::decode(tokens)?))
+    }
+}
+
+impl Encode for [usize; 2] {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        (self[0], self[1]).encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        let (a, b) = Encode::decode(tokens)?;
+        Some([a, b])
+    }
+}
+
+impl Encode for Keybind {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.activate.encode(tokens);
+        self.disable.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(Keybind {
+            activate: Encode::decode(tokens)?,
+            disable: Encode::decode(tokens)?,
+            forced: None,
+        })
+    }
+}
+
+impl Encode for LinearSpring {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.target_distance.encode(tokens);
+        self.force_constant.encode(tokens);
+        self.damping.encode(tokens);
+        self.compression.encode(tokens);
+        self.tension.encode(tokens);
+        self.maximum_force.encode(tokens);
+        self.maximum_damping.encode(tokens);
+        self.destroy_on_maximum.encode(tokens);
+        self.minimum_length.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(LinearSpring {
+            target_distance: Encode::decode(tokens)?,
+            force_constant: Encode::decode(tokens)?,
+            damping: Encode::decode(tokens)?,
+            compression: Encode::decode(tokens)?,
+            tension: Encode::decode(tokens)?,
+            maximum_force: Encode::decode(tokens)?,
+            maximum_damping: Encode::decode(tokens)?,
+            destroy_on_maximum: Encode::decode(tokens)?,
+            minimum_length: Encode::decode(tokens)?,
+        })
+    }
+}
+
+impl Encode for AngularSpring {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.target_angle.encode(tokens);
+        self.force_constant.encode(tokens);
+        self.damping.encode(tokens);
+        self.inwards.encode(tokens);
+        self.outwards.encode(tokens);
+        self.maximum_force.encode(tokens);
+        self.maximum_damping.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(AngularSpring {
+            target_angle: Encode::decode(tokens)?,
+            force_constant: Encode::decode(tokens)?,
+            damping: Encode::decode(tokens)?,
+            inwards: Encode::decode(tokens)?,
+            outwards: Encode::decode(tokens)?,
+            maximum_force: Encode::decode(tokens)?,
+            maximum_damping: Encode::decode(tokens)?,
+        })
+    }
+}
+
+/// Leaves out the velocity, impulse, and constraint, which can't be carried over to a new scene
+impl Encode for Point {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.position.encode(tokens);
+        self.mass.encode(tokens);
+        self.spring.encode(tokens);
+        self.num_connections.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(Point {
+            position: Encode::decode(tokens)?,
+            mass: f32::decode(tokens)?.max(Point::MINIMUM_MASS),
+            spring: Encode::decode(tokens)?,
+            num_connections: Encode::decode(tokens)?,
+            ..Default::default()
+        })
+    }
+}
+
+impl Encode for Line {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.spring.encode(tokens);
+        self.friction.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(Line {
+            spring: Encode::decode(tokens)?,
+            friction: Encode::decode(tokens)?,
+        })
+    }
+}
+
+impl Encode for SpringIndex {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        let (kind, index) = match self {
+            SpringIndex::Edge(index) => ("edge", index),
+            SpringIndex::Internal(index) => ("internal", index),
+        };
+
+        tokens.push(kind.to_owned());
+        index.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        match tokens.next()? {
+            "edge" => Some(SpringIndex::Edge(Encode::decode(tokens)?)),
+            "internal" => Some(SpringIndex::Internal(Encode::decode(tokens)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Encode for ConnectionState {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        tokens.push(
+            match self {
+                ConnectionState::Source => "source",
+                ConnectionState::Connected => "connected",
+                ConnectionState::Disconnected => "disconnected",
+            }
+            .to_owned(),
+        );
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        match tokens.next()? {
+            "source" => Some(ConnectionState::Source),
+            "connected" => Some(ConnectionState::Connected),
+            "disconnected" => Some(ConnectionState::Disconnected),
+            _ => None,
+        }
+    }
+}
+
+impl Encode for Gimbal {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        self.max_angle.encode(tokens);
+        self.steer_left.encode(tokens);
+        self.steer_right.encode(tokens);
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        Some(Gimbal {
+            max_angle: Encode::decode(tokens)?,
+            angle: 0.0,
+            steer_left: Encode::decode(tokens)?,
+            steer_right: Encode::decode(tokens)?,
+        })
+    }
+}
+
+impl Encode for Actor {
+    fn encode(&self, tokens: &mut Vec<String>) {
+        match self {
+            Actor::RocketMotor {
+                line,
+                force,
+                enable,
+                max_particle_time,
+                gimbal,
+                exhaust_start,
+                exhaust_end,
+                ..
+            } => {
+                tokens.push("rocket".to_owned());
+                line.encode(tokens);
+                force.encode(tokens);
+                enable.encode(tokens);
+                max_particle_time.encode(tokens);
+                gimbal.encode(tokens);
+                exhaust_start.encode(tokens);
+                exhaust_end.encode(tokens);
+            }
+            Actor::HabitatBubble {
+                minimum_pressure,
+                fuel,
+                fuel_capacity,
+            } => {
+                tokens.push("habitat".to_owned());
+                minimum_pressure.encode(tokens);
+                fuel.encode(tokens);
+                fuel_capacity.encode(tokens);
+            }
+            Actor::Piston { lengths, enable } => {
+                tokens.push("piston".to_owned());
+                lengths.encode(tokens);
+                enable.encode(tokens);
+            }
+            Actor::ReactionWheel {
+                torque,
+                enable_cw,
+                enable_ccw,
+            } => {
+                tokens.push("wheel".to_owned());
+                torque.encode(tokens);
+                enable_cw.encode(tokens);
+                enable_ccw.encode(tokens);
+            }
+            Actor::OrientationSpring {
+                line,
+                target_direction,
+                stiffness,
+                damping,
+            } => {
+                tokens.push("orientation".to_owned());
+                line.encode(tokens);
+                target_direction.encode(tokens);
+                stiffness.encode(tokens);
+                damping.encode(tokens);
+            }
+            Actor::Label { offset, text, size } => {
+                tokens.push("label".to_owned());
+                offset.encode(tokens);
+                text.encode(tokens);
+                size.encode(tokens);
+            }
+        }
+    }
+
+    fn decode(tokens: &mut SplitWhitespace) -> Option<Self> {
+        match tokens.next()? {
+            "rocket" => Some(Actor::RocketMotor {
+                line: Encode::decode(tokens)?,
+                force: Encode::decode(tokens)?,
+                enable: Encode::decode(tokens)?,
+                particle_time: 0.0,
+                max_particle_time: Encode::decode(tokens)?,
+                gimbal: Encode::decode(tokens)?,
+                exhaust_start: Encode::decode(tokens)?,
+                exhaust_end: Encode::decode(tokens)?,
+            }),
+            "habitat" => Some(Actor::HabitatBubble {
+                minimum_pressure: Encode::decode(tokens)?,
+                fuel: Encode::decode(tokens)?,
+                fuel_capacity: Encode::decode(tokens)?,
+            }),
+            "piston" => Some(Actor::Piston {
+                lengths: Encode::decode(tokens)?,
+                enable: Encode::decode(tokens)?,
+            }),
+            "label" => Some(Actor::Label {
+                offset: Encode::decode(tokens)?,
+                text: Encode::decode(tokens)?,
+                size: Encode::decode(tokens)?,
+            }),
+            "wheel" => Some(Actor::ReactionWheel {
+                torque: Encode::decode(tokens)?,
+                enable_cw: Encode::decode(tokens)?,
+                enable_ccw: Encode::decode(tokens)?,
+            }),
+            "orientation" => Some(Actor::OrientationSpring {
+                line: Encode::decode(tokens)?,
+                target_direction: Vec2::decode(tokens)?.normalize_or(Vec2::X),
+                stiffness: Encode::decode(tokens)?,
+                damping: Encode::decode(tokens)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soft_body::SoftBodyBuilder;
+
+    /// A unit square with a seam of two points along its right edge
+    fn square(offset: Vec2) -> SoftBody {
+        SoftBodyBuilder::default()
+            .offset_ex(offset)
+            .point(0.0, 0.0)
+            .point(1.0, 0.0)
+            .with_attatchment_point(2)
+            .point(1.0, 1.0)
+            .point(0.0, 1.0)
+            .build()
+    }
+
+    /// Two squares with their seams connected, the way they were copied before seams could be
+    /// joined at junctions
+    fn legacy_ship(soft_bodies: &[SoftBody; 2]) -> String {
+        let mut tokens = vec![LEGACY_HEADER.to_owned()];
+
+        soft_bodies.len().encode(&mut tokens);
+
+        for (i, soft_body) in soft_bodies.iter().enumerate() {
+            // Point 1 of the first square is held to point 2 of the second, and point 2 to point 1
+            soft_body
+                .shape
+                .iter()
+                .enumerate()
+                .map(|(index, &(point, line))| {
+                    let constraint = match (i, index) {
+                        (0, 1) | (1, 2) => Some(0_usize),
+                        (0, 2) | (1, 1) => Some(1),
+                        _ => None,
+                    };
+
+                    (point, line, constraint)
+                })
+                .collect::<Vec<_>>()
+                .encode(&mut tokens);
+            soft_body.internal_springs.encode(&mut tokens);
+            soft_body.gas_force.encode(&mut tokens);
+            vec![(1_usize, 2_usize, Some((1 - i, 0_usize)))].encode(&mut tokens);
+            soft_body.actors.encode(&mut tokens);
+            soft_body.connection_state.encode(&mut tokens);
+            soft_body.collision_layer.encode(&mut tokens);
+            soft_body.collision_mask.encode(&mut tokens);
+            soft_body.health.encode(&mut tokens);
+            soft_body.frozen.encode(&mut tokens);
+            soft_body.id.encode(&mut tokens);
+        }
+
+        vec![vec![(0_usize, 1_usize), (1, 2)], vec![(0, 2), (1, 1)]].encode(&mut tokens);
+
+        tokens.join(" ")
+    }
+
+    #[test]
+    fn loads_legacy_ships() {
+        let mut soft_bodies = [square(Vec2::ZERO), square(vec2(1.0, 0.0))];
+
+        for soft_body in &mut soft_bodies {
+            for spring in soft_body.linear_springs_mut() {
+                spring.force_constant = 100.0;
+                spring.maximum_force = 0.5;
+            }
+        }
+
+        let simulation = load(&legacy_ship(&soft_bodies)).unwrap();
+
+        assert_eq!(simulation.soft_bodies.len(), 2);
+        assert_eq!(simulation.constraints.len(), 2);
+
+        for &key in &simulation.keys {
+            let soft_body = &simulation.soft_bodies[key];
+
+            assert_eq!(soft_body.attatchment_points[0].connections.len(), 1);
+
+            for (_, line) in &soft_body.shape {
+                assert_eq!(line.spring.maximum_force, 50.0);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_ships() {
+        let mut simulation = Simulation::new();
+
+        let keys = [square(Vec2::ZERO), square(vec2(1.0, 0.0))]
+            .map(|soft_body| simulation.soft_bodies.insert(soft_body));
+        simulation
+            .connect_attatchment_points(keys.map(|soft_body| AttatchmentPointHandle {
+                soft_body,
+                index: 0,
+            }))
+            .unwrap();
+
+        let text = save(&simulation, &keys);
+        let loaded = load(&text).unwrap();
+
+        assert_eq!(loaded.soft_bodies.len(), 2);
+        assert_eq!(loaded.constraints.len(), 2);
+        assert_eq!(save(&loaded, &loaded.keys), text);
+    }
+}
@@ -1,20 +1,34 @@
-use egui::{Button, Context, Label, Sense, Slider, Ui};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    f32::consts::{FRAC_PI_4, PI, TAU},
+    fmt, mem,
+    ops::RangeInclusive,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use egui::{Button, Context, Label, Sense, Slider, SliderClamping, StrokeKind, Ui};
 use macroquad::{
     camera::Camera2D,
     color::{Color, colors},
     input::{self, KeyCode, MouseButton},
     math::{Vec2, vec2},
-    window,
+    rand, shapes, time, window,
 };
+use nalgebra::Vector2;
 use slotmap::{HopSlotMap, new_key_type};
 
 use crate::{
     constraint::{Constraint, PointHandle},
-    particle::Particle,
+    particle::{Particle, Shape},
+    prefabs::{self, Prefab},
+    scenario::WinCondition,
+    ship_clipboard,
     soft_body::{
-        Actor, AttatchmentPointHandle, BoundingBox, ConnectionState, JoiningSpring, Keybind,
-        LinearSpring, Point, SoftBody,
+        Actor, AttatchmentPointHandle, BoundingBox, ConnectionState, Gimbal, Integrator,
+        JoiningSpring, Keybind, LinearSpring, Point, SoftBody, SoftBodySnapshot,
     },
+    stars::AddOffset,
     utils,
 };
 
@@ -27,7 +41,212 @@ pub struct Simulation {
 
     pub constraints: HopSlotMap<ConstraintKey, Constraint>,
 
+    /// Collects every significant collision until it is drained with
+    /// [`Self::drain_collision_events`]
+    pub collision_events: Vec<CollisionEvent>,
+
+    pub triggers: Vec<Trigger>,
+    /// Collects every time a soft body enters or exits a trigger until it is drained with
+    /// [`Self::drain_trigger_events`]
+    pub trigger_events: Vec<TriggerEvent>,
+
+    /// Where soft bodies folded over themselves and were destroyed, and how long ago. Drawn in
+    /// debug mode.
+    pub folds: Vec<(Vec2, f32)>,
+    /// Whether soft bodies which fold over themselves are pushed apart for a few ticks before
+    /// being destroyed. See [`Self::MAXIMUM_FOLDED_TICKS`]. Off by default, so folded soft bodies
+    /// are destroyed straight away as before.
+    pub fold_recovery: bool,
+
+    /// The oldest debris is removed while there are more soft bodies than this, so destructions
+    /// can't slow the simulation down forever. See [`Self::enforce_body_budget`]
+    pub max_bodies: usize,
+    /// Whether there are more soft bodies than [`Self::max_bodies`] even without any debris,
+    /// which is shown as a warning instead of removing anything the player built
+    pub over_budget: bool,
+
+    pub integrator: Integrator,
+
+    /// Soft bodies with a point that moves further than this in one tick are moved again in
+    /// smaller steps, checking for collisions after each, so they don't pass through thin soft
+    /// bodies. See [`Self::substep_fast_soft_bodies`].
+    pub max_displacement_per_tick: f32,
+    /// Whether to also check the path each point took during a tick for collisions. See
+    /// [`SoftBody::check_swept_points_against_other`]
+    pub continuous_collision: bool,
+
+    /// Keeps soft bodies from drifting away forever. See [`Self::apply_world_boundary`]
+    pub world_boundary: Option<WorldBoundary>,
+
+    /// The goal of the scenario being played, if any. See [`crate::scenario::Scenario`]
+    pub win_condition: Option<WinCondition>,
+    /// Set by [`Self::update_objective`] once the win condition is met, and left set afterwards
+    pub objective_complete: bool,
+    /// Set on the tick the objective is completed, until it is taken with
+    /// [`Self::take_win_event`]
+    pub win_event: bool,
+
+    /// Drawn over every visible soft body by [`Self::draw`]. See [`Self::add_overlay`]
+    pub overlays: Vec<Overlay>,
+
+    /// Added to by every tick, and shown and reset by [`Self::update_gui`]
+    pub phase_timings: PhaseTimings,
+    /// Whether [`Self::update_gui`] shows [`Self::phase_timings`]
+    pub show_phase_timings: bool,
+    /// Whether debug mode draws the velocity of every point. See [`SoftBody::draw_velocities`]
+    pub show_velocities: bool,
+    /// How long the velocity arrows are per unit of speed
+    pub velocity_arrow_scale: f32,
+
+    /// Whether the recent path of the selected soft body is recorded and drawn
+    pub show_trace: bool,
+    /// The centroids of [`Self::trace_body`], oldest first, recorded every
+    /// [`Self::TRACE_INTERVAL`] ticks. See [`Self::update_trace`]
+    pub trace: VecDeque<Vec2>,
+    /// The soft body [`Self::trace`] follows. The trace starts over when it changes.
+    pub trace_body: Option<SoftBodyKey>,
+    /// Ticks since the last position was added to [`Self::trace`]
+    pub trace_ticks: usize,
+
+    /// Pulls whatever is being dragged towards the mouse. See [`Self::DEFAULT_GRAB_SPRING`]
+    pub grab_spring: JoiningSpring,
+    /// Pulls attatchment points which are being connected together. See
+    /// [`Self::DEFAULT_PULL_SPRING`]
+    pub pull_spring: JoiningSpring,
+    /// How quickly a soft body dragged on its own stops spinning, as a fraction of its angular
+    /// velocity per second. Zero leaves it free to spin.
+    pub grab_angular_damping: f32,
+
     pub input_state: InputState,
+    pub controls: Controls,
+}
+
+/// Totals over the connected ship of a soft body, shown in the editor. See
+/// [`Simulation::ship_stats`]
+#[derive(Clone, Copy, Debug)]
+pub struct ShipStats {
+    /// The sum of the force of every rocket motor
+    pub thrust: f32,
+    pub mass: f32,
+    /// How fast the ship speeds up with every rocket motor firing in the same direction
+    pub max_acceleration: f32,
+    /// How much the ship can change its velocity by with the fuel it has left, or `None` if it
+    /// has no habitat bubble to hold fuel
+    pub delta_v: Option<f32>,
+}
+
+/// How long each part of [`Simulation::tick_simulation`] took, added up over every tick since it
+/// was last reset
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhaseTimings {
+    /// Actors, springs, pressure, and moving the points
+    pub forces: Duration,
+    pub constraints: Duration,
+    /// See [`Simulation::substep_fast_soft_bodies`]
+    pub substeps: Duration,
+    /// Finding the pairs of soft bodies whose bounding boxes overlap
+    pub broad_phase: Duration,
+    /// Resolving the collisions between those pairs
+    pub narrow_phase: Duration,
+    /// Particles, destroying soft bodies, the world boundary, and triggers
+    pub cleanup: Duration,
+    pub ticks: usize,
+}
+
+impl PhaseTimings {
+    pub fn iter(&self) -> [(&'static str, Duration); 6] {
+        [
+            ("Forces", self.forces),
+            ("Constraints", self.constraints),
+            ("Substeps", self.substeps),
+            ("Broad phase", self.broad_phase),
+            ("Narrow phase", self.narrow_phase),
+            ("Cleanup", self.cleanup),
+        ]
+    }
+
+    pub fn total(&self) -> Duration {
+        self.iter().into_iter().map(|(_, duration)| duration).sum()
+    }
+
+    /// Adds the time since `start` to `phase`, and starts timing the next phase
+    fn lap(phase: &mut Duration, start: &mut Instant) {
+        let now = Instant::now();
+
+        *phase += now - *start;
+        *start = now;
+    }
+}
+
+/// Draws extra things such as labels or health bars over a soft body, without changing
+/// [`Simulation::draw`]
+#[derive(Clone)]
+pub struct Overlay(pub Rc<OverlayFn>);
+
+pub type OverlayFn = dyn Fn(SoftBodyKey, &SoftBody);
+
+impl fmt::Debug for Overlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Overlay")
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct WorldBoundary {
+    pub area: BoundingBox,
+    pub mode: BoundaryMode,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryMode {
+    /// Points which leave the area are put back on its edge with their outwards velocity
+    /// reflected
+    Bounce,
+    /// Ships whose center of mass leaves the area are moved to the opposite edge. The star field only
+    /// lines up across the edge if the size of the area is a multiple of its map size.
+    Wrap,
+}
+
+/// Moves every position in the world, leaving velocities and the controls alone. Constraints only
+/// refer to points by their handles, so they don't need to be moved.
+impl AddOffset for Simulation {
+    fn add_offset(&mut self, offset: Vector2<f32>) {
+        for (_, soft_body) in &mut self.soft_bodies {
+            soft_body.add_offset(offset);
+        }
+
+        for particle in &mut self.particles {
+            particle.add_offset(offset);
+        }
+
+        let offset = vec2(offset.x, offset.y);
+
+        for trigger in &mut self.triggers {
+            trigger.area.min_corner += offset;
+        }
+
+        for (position, _) in &mut self.folds {
+            *position += offset;
+        }
+
+        for position in &mut self.trace {
+            *position += offset;
+        }
+
+        if let Some(world_boundary) = &mut self.world_boundary {
+            world_boundary.area.min_corner += offset;
+        }
+
+        if let Some(WinCondition::BodyInZone { area, .. }) = &mut self.win_condition {
+            area.min_corner += offset;
+        }
+
+        self.input_state.mouse.position += offset;
+
+        if let Some(selection_start) = &mut self.input_state.selection_start {
+            *selection_start += offset;
+        }
+    }
 }
 
 new_key_type! {
@@ -35,9 +254,82 @@ new_key_type! {
     pub struct ConstraintKey;
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    pub position: Vec2,
+    /// The magnitude of the impulse along the normal of the collision
+    pub impulse: f32,
+}
+
+/// An area which tracks the soft bodies inside it without colliding with them
+#[derive(Clone, Debug)]
+pub struct Trigger {
+    pub area: BoundingBox,
+    pub bodies_inside: Vec<SoftBodyKey>,
+    /// How much fuel per second is given to the habitat bubbles inside
+    pub refuel_rate: f32,
+}
+
+impl Trigger {
+    pub fn new(area: BoundingBox) -> Self {
+        Self {
+            area,
+            bodies_inside: Vec::new(),
+            refuel_rate: 0.0,
+        }
+    }
+
+    pub fn refuel_zone(area: BoundingBox, refuel_rate: f32) -> Self {
+        Self {
+            refuel_rate,
+            ..Self::new(area)
+        }
+    }
+
+    /// A soft body is inside if any of its points are inside, or if it surrounds the center of the
+    /// area.
+    pub fn contains(&self, soft_body: &SoftBody) -> bool {
+        Self::area_contains(&self.area, soft_body)
+    }
+
+    pub fn area_contains(area: &BoundingBox, soft_body: &SoftBody) -> bool {
+        area.intersects_other(&soft_body.bounding_box)
+            && (soft_body
+                .shape
+                .iter()
+                .any(|(point, _)| area.contains_point(point.position))
+                || soft_body.contains_point(area.min_corner + area.size / 2.0))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerEvent {
+    /// `trigger` is an index into [`Simulation::triggers`]
+    Enter {
+        trigger: usize,
+        soft_body: SoftBodyKey,
+    },
+    /// Also sent when a soft body inside the trigger is removed
+    Exit {
+        trigger: usize,
+        soft_body: SoftBodyKey,
+    },
+}
+
+/// See [`Simulation::snapshot`]
+#[derive(Clone, Debug)]
+pub struct SimulationSnapshot {
+    pub soft_bodies: Vec<(SoftBodyKey, SoftBodySnapshot)>,
+    pub particles: Vec<Particle>,
+}
+
 #[derive(Clone, Debug)]
 pub struct InputState {
     pub selected_attatchment_point: Option<(AttatchmentPointHandle, f32)>,
+    /// A soft body which was clicked inside of away from any attatchment point, the edge closest
+    /// to where it was clicked, and how far along that edge. See
+    /// [`Simulation::push_line_towards_mouse`]
+    pub grabbed_line: Option<(SoftBodyKey, usize, f32)>,
     pub target_attatchment_point: Option<AttatchmentPointHandle>,
     pub can_connect: bool,
 
@@ -45,22 +337,79 @@ pub struct InputState {
     pub clicking: bool,
 
     pub ui_hovered: bool,
+    /// Whether a text field has keyboard focus, so the keys typed into it don't reach the actors
+    pub typing: bool,
+    /// Keys which were pressed to rebind something or typed into a text field, and are ignored by
+    /// every keybind until they are released. See [`InputState::update_suppressed_keys`]
+    pub suppressed_keys: Vec<KeyCode>,
 
     pub mouse: Point,
 
     pub editing: bool,
     pub selected_soft_body: Option<SoftBodyKey>,
 
-    pub keybind_focus: Option<KeybindFocus>,
+    /// Which keybind in the editor is being rebound, in the order they are shown, and which of
+    /// its keys
+    pub keybind_focus: Option<(usize, KeybindFocus)>,
+    /// The index into [`Controls::iter_mut`] of the control being rebound
+    pub controls_focus: Option<usize>,
 
     pub following_camera: bool,
     pub show_respawn_message: bool,
+
+    /// Dims every soft body except the selected one
+    pub solo: bool,
+    /// Draws every attatchment point as if it were selected, with its length, so seams can be
+    /// found without hovering over them
+    pub show_attatchment_points: bool,
+    /// Only attatchment points of this length can be hovered over, so seams of other lengths
+    /// don't get in the way
+    pub seam_length_filter: Option<usize>,
+
+    /// The index into [`prefabs::PALETTE`] of the prefab to spawn at the next click
+    pub spawning: Option<usize>,
+    /// The prefab being spawned, following the mouse, and its index into [`prefabs::PALETTE`]
+    pub spawn_preview: Option<(usize, SoftBody)>,
+    /// Whether the spawn preview overlaps another soft body, so it can't be placed
+    pub spawn_blocked: bool,
+
+    /// A command which is waiting for the user to confirm it
+    pub confirming: Option<SceneCommand>,
+
+    /// Soft bodies with keybinds picked by dragging a box with the right mouse button, which the
+    /// selected soft body's keybinds can be copied to
+    pub selection: Vec<SoftBodyKey>,
+    /// Where the right mouse button was pressed to start dragging a selection box
+    pub selection_start: Option<Vec2>,
+
+    /// The result of the last copy or paste, and the time it happened at
+    pub clipboard_message: Option<(String, f64)>,
+
+    /// Whether the followed ship is being held still with [`Simulation::autopilot_hold`]
+    pub autopilot: bool,
+}
+
+/// Commands which throw away the whole scene, so they must be confirmed first
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SceneCommand {
+    Clear,
+    Reset,
+}
+
+impl SceneCommand {
+    pub fn description(self) -> &'static str {
+        match self {
+            SceneCommand::Clear => "Remove everything from the scene?",
+            SceneCommand::Reset => "Replace the scene with the default one?",
+        }
+    }
 }
 
 impl Default for InputState {
     fn default() -> Self {
         Self {
             selected_attatchment_point: None,
+            grabbed_line: None,
             target_attatchment_point: None,
             can_connect: false,
 
@@ -68,6 +417,8 @@ impl Default for InputState {
             clicking: false,
 
             ui_hovered: false,
+            typing: false,
+            suppressed_keys: Vec::new(),
 
             mouse: Point::default(),
 
@@ -75,13 +426,134 @@ impl Default for InputState {
             selected_soft_body: None,
 
             keybind_focus: None,
+            controls_focus: None,
 
             following_camera: false,
             show_respawn_message: false,
+
+            solo: false,
+            show_attatchment_points: false,
+            seam_length_filter: None,
+
+            spawning: None,
+            spawn_preview: None,
+            spawn_blocked: false,
+
+            confirming: None,
+
+            selection: Vec::new(),
+            selection_start: None,
+
+            clipboard_message: None,
+
+            autopilot: false,
+        }
+    }
+}
+
+impl InputState {
+    /// Stops suppressing keys which are no longer held, then suppresses `last_key_pressed` if the
+    /// editor is capturing keys this frame, so it doesn't also fire the actors bound to it. Should
+    /// be called once per frame.
+    pub fn update_suppressed_keys(
+        &mut self,
+        last_key_pressed: Option<KeyCode>,
+        is_key_down: impl Fn(KeyCode) -> bool,
+    ) {
+        self.suppressed_keys
+            .retain(|&key_code| is_key_down(key_code));
+
+        if (self.keybind_focus.is_some() || self.controls_focus.is_some() || self.typing)
+            && let Some(key_code) = last_key_pressed
+            && !self.suppressed_keys.contains(&key_code)
+        {
+            self.suppressed_keys.push(key_code);
+        }
+    }
+
+    /// Whether `key_code` was pressed this frame for a control, rather than typed into a text
+    /// field or captured by the keybind or controls editor. Expects
+    /// [`Self::update_suppressed_keys`] to have been called earlier in the frame.
+    pub fn is_control_pressed(&self, key_code: KeyCode) -> bool {
+        input::is_key_pressed(key_code) && !self.suppressed_keys.contains(&key_code)
+    }
+}
+
+/// Keys for actions which aren't part of any soft body, as opposed to the [`Keybind`]s of actors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Controls {
+    pub fullscreen: KeyCode,
+    pub debug: KeyCode,
+    /// The Pause key by default, since actors are free to bind Space and every function key is
+    /// already taken
+    pub pause: KeyCode,
+    pub menu: KeyCode,
+    pub solo: KeyCode,
+    pub align: KeyCode,
+    pub clear: KeyCode,
+    pub reset: KeyCode,
+    /// Held while grabbing to drag the whole connected ship instead of one seam
+    pub drag_ship: KeyCode,
+    pub stress_map: KeyCode,
+    /// Toggles [`Simulation::autopilot_hold`] on the followed ship
+    pub autopilot: KeyCode,
+    pub grid: KeyCode,
+    /// Toggles [`Simulation::show_velocities`]
+    pub velocities: KeyCode,
+    /// Toggles [`SoftBody::frozen`] on the soft body being edited
+    pub freeze: KeyCode,
+    /// Toggles [`InputState::show_attatchment_points`]
+    pub attatchment_points: KeyCode,
+    /// Toggles [`Simulation::show_trace`]
+    pub trace: KeyCode,
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Self {
+            fullscreen: KeyCode::F11,
+            debug: KeyCode::F3,
+            pause: KeyCode::Pause,
+            menu: KeyCode::F1,
+            solo: KeyCode::F2,
+            align: KeyCode::F4,
+            clear: KeyCode::F9,
+            reset: KeyCode::F10,
+            drag_ship: KeyCode::LeftAlt,
+            stress_map: KeyCode::F5,
+            autopilot: KeyCode::F6,
+            grid: KeyCode::F7,
+            velocities: KeyCode::F12,
+            freeze: KeyCode::Insert,
+            attatchment_points: KeyCode::Home,
+            trace: KeyCode::End,
         }
     }
 }
 
+impl Controls {
+    pub fn iter_mut(&mut self) -> [(&'static str, &mut KeyCode); 16] {
+        [
+            ("Fullscreen", &mut self.fullscreen),
+            ("Debug view", &mut self.debug),
+            ("Pause", &mut self.pause),
+            ("Menu", &mut self.menu),
+            ("Solo view", &mut self.solo),
+            ("Align seam", &mut self.align),
+            ("Clear scene", &mut self.clear),
+            ("Reset scene", &mut self.reset),
+            ("Drag ship", &mut self.drag_ship),
+            ("Stress map", &mut self.stress_map),
+            ("Autopilot", &mut self.autopilot),
+            ("Grid", &mut self.grid),
+            ("Velocity arrows", &mut self.velocities),
+            ("Freeze body", &mut self.freeze),
+            ("Show seams", &mut self.attatchment_points),
+            ("Trace path", &mut self.trace),
+        ]
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum KeybindFocus {
     Activate(usize),
@@ -90,8 +562,14 @@ pub enum KeybindFocus {
     NewDisable,
 }
 
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Simulation {
-    pub const GRAB_SPRING: JoiningSpring = JoiningSpring {
+    pub const DEFAULT_GRAB_SPRING: JoiningSpring = JoiningSpring {
         force_constant: 10.0,
         normal_damping: 2.5,
         perpendicular_damping: 5.0,
@@ -102,7 +580,7 @@ impl Simulation {
         maximum_perpendicular_damping: 50.0,
     };
 
-    pub const PULL_SPRING: JoiningSpring = JoiningSpring {
+    pub const DEFAULT_PULL_SPRING: JoiningSpring = JoiningSpring {
         force_constant: 10.0,
         normal_damping: 2.5,
         perpendicular_damping: 10.0,
@@ -119,13 +597,81 @@ impl Simulation {
         damping: 50.0,
         compression: true,
         tension: true,
-        maximum_force: 50.0,
+        maximum_force: 5000.0,
         maximum_damping: 50.0,
         destroy_on_maximum: false,
+        minimum_length: 0.0,
     };
 
     pub const MAXIMUM_ATTATCHMENT_DISTANCE: f32 = 0.5;
 
+    /// How far the arrow keys move the selected soft body per press
+    pub const NUDGE_DISTANCE: f32 = 0.05;
+    /// How far Shift + the arrow keys rotate the selected soft body per press, in radians
+    pub const NUDGE_ANGLE: f32 = TAU / 180.0;
+
+    /// How many seconds the result of a copy or paste is shown for
+    pub const CLIPBOARD_MESSAGE_DURATION: f64 = 3.0;
+
+    /// How opaque the darkening drawn over soft bodies other than the selected one is in solo mode
+    pub const SOLO_DIMMING: f32 = 0.75;
+
+    /// The color of the preview of the prefab being spawned
+    pub const SPAWN_PREVIEW_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.25);
+    /// The color of the preview of the prefab being spawned when it overlaps another soft body
+    pub const SPAWN_BLOCKED_COLOR: Color = Color::new(1.0, 0.0, 0.0, 0.25);
+
+    /// The sparks given off by a seam when it is connected
+    pub const CONNECT_PARTICLE_COLOR: Color = colors::SKYBLUE;
+    /// The puff given off by a seam when it is disconnected
+    pub const DISCONNECT_PARTICLE_COLOR: Color = colors::ORANGE;
+    /// See [`Self::emit_seam_particles`]
+    pub const SEAM_PARTICLES_PER_POINT: usize = 4;
+
+    /// See [`Self::max_bodies`]
+    pub const DEFAULT_MAX_BODIES: usize = 500;
+
+    /// The longest seam length [`InputState::seam_length_filter`] can be set to in the menu
+    pub const MAX_SEAM_LENGTH_FILTER: usize = 32;
+
+    /// The most other attatchment points one attatchment point can be connected to at once
+    pub const MAX_SEAM_CONNECTIONS: usize = 3;
+
+    /// The most steps a fast soft body's movement is split into in one tick
+    pub const MAXIMUM_SUBSTEPS: usize = 8;
+
+    /// How many ticks in a row a soft body can stay folded over itself before it is destroyed
+    pub const MAXIMUM_FOLDED_TICKS: usize = 10;
+
+    /// How long a fold is drawn for in debug mode, in seconds
+    pub const FOLD_MARKER_TIME: f32 = 3.0;
+
+    /// How many ticks apart the positions in [`Self::trace`] are
+    pub const TRACE_INTERVAL: usize = 4;
+    /// The most positions kept in [`Self::trace`], after which the oldest is dropped
+    pub const TRACE_LENGTH: usize = 300;
+    /// The newest end of the trace, which fades out towards the oldest end
+    pub const TRACE_COLOR: Color = colors::YELLOW;
+    pub const TRACE_THICKNESS: f32 = 0.03;
+
+    /// How far the followed habitat bubble can get from the origin before everything is moved
+    /// back. See [`Self::recenter`]
+    pub const RECENTER_DISTANCE: f32 = 1000.0;
+
+    /// How much fuel a rocket motor uses per unit of impulse
+    pub const FUEL_PER_IMPULSE: f32 = 0.01;
+    pub const REFUEL_ZONE_COLOR: Color = Color::new(0.0, 0.5, 0.0, 0.25);
+    /// The sparks given off by a habitat bubble when it enters a refuel zone
+    pub const REFUEL_PARTICLE_COLOR: Color = colors::LIME;
+    /// The color of the zone and soft bodies in [`Self::win_condition`]
+    pub const OBJECTIVE_COLOR: Color = Color::new(1.0, 0.8, 0.0, 0.25);
+
+    /// The correction per tick at which a connected seam is drawn completely red
+    pub const MAXIMUM_SEAM_STRESS: f32 = 0.01;
+
+    /// The pressure at which a soft body is filled completely red in the stress map
+    pub const STRESS_MAP_MAXIMUM_PRESSURE: f32 = 6.0;
+
     pub fn new() -> Self {
         Self {
             soft_bodies: HopSlotMap::default(),
@@ -135,47 +681,203 @@ impl Simulation {
 
             constraints: HopSlotMap::default(),
 
+            collision_events: Vec::new(),
+
+            triggers: Vec::new(),
+            trigger_events: Vec::new(),
+
+            folds: Vec::new(),
+            fold_recovery: false,
+
+            max_bodies: Self::DEFAULT_MAX_BODIES,
+            over_budget: false,
+
+            integrator: Integrator::default(),
+
+            max_displacement_per_tick: 0.1,
+            continuous_collision: false,
+
+            world_boundary: None,
+
+            win_condition: None,
+            objective_complete: false,
+            win_event: false,
+
+            overlays: Vec::new(),
+
+            phase_timings: PhaseTimings::default(),
+            show_phase_timings: false,
+            show_velocities: false,
+            velocity_arrow_scale: 0.1,
+
+            show_trace: false,
+            trace: VecDeque::new(),
+            trace_body: None,
+            trace_ticks: 0,
+
+            grab_spring: Self::DEFAULT_GRAB_SPRING,
+            pull_spring: Self::DEFAULT_PULL_SPRING,
+            grab_angular_damping: 0.0,
+
             input_state: InputState::default(),
+            controls: Controls::default(),
         }
     }
 
-    pub fn draw(&self, debug: bool, bounding_box: BoundingBox) {
+    /// Calls `overlay` with every soft body within the view each time the simulation is drawn,
+    /// after the soft bodies, their actors, and their seams
+    pub fn add_overlay(&mut self, overlay: impl Fn(SoftBodyKey, &SoftBody) + 'static) {
+        self.overlays.push(Overlay(Rc::new(overlay)));
+    }
+
+    /// Only draws what is within `view`, which should be [`utils::visible_world_rect`]
+    pub fn draw(&self, debug: bool, stress_map: bool, view: BoundingBox) {
         for particle in &self.particles {
-            if bounding_box.is_point_within_distance(particle.position, particle.size()) {
+            if view.is_point_within_distance(particle.position, particle.size()) {
                 particle.draw();
             }
         }
 
+        for trigger in &self.triggers {
+            if trigger.refuel_rate > 0.0 {
+                let area = trigger.area;
+
+                shapes::draw_rectangle(
+                    area.min_corner.x,
+                    area.min_corner.y,
+                    area.size.x,
+                    area.size.y,
+                    Self::REFUEL_ZONE_COLOR,
+                );
+            }
+        }
+
+        if let Some(win_condition) = self.win_condition {
+            if let WinCondition::BodyInZone { area, .. } = win_condition {
+                shapes::draw_rectangle(
+                    area.min_corner.x,
+                    area.min_corner.y,
+                    area.size.x,
+                    area.size.y,
+                    Self::OBJECTIVE_COLOR,
+                );
+            }
+
+            for key in win_condition.soft_bodies() {
+                if let Some(soft_body) = self.soft_bodies.get(key) {
+                    soft_body.fill_color(Self::OBJECTIVE_COLOR);
+                }
+            }
+        }
+
         for (_, soft_body) in &self.soft_bodies {
-            if bounding_box.is_other_within_distance(&soft_body.bounding_box, 0.2) {
-                soft_body.draw_actors_back();
+            if soft_body.is_visible_in(&view) {
+                soft_body.draw_actors_back(&self.input_state.suppressed_keys);
             }
         }
 
         for (_, soft_body) in &self.soft_bodies {
-            if bounding_box.is_other_within_distance(&soft_body.bounding_box, 0.2) {
+            if soft_body.is_visible_in(&view) {
                 soft_body.draw();
             }
         }
 
-        if let Some(selected) = self.input_state.selected_soft_body {
-            if let Some(soft_body) = self.soft_bodies.get(selected) {
-                soft_body.outline_color(0.05, colors::BLUE);
+        if stress_map {
+            for (_, soft_body) in &self.soft_bodies {
+                if soft_body.is_visible_in(&view) {
+                    soft_body.draw_stress_map(Self::STRESS_MAP_MAXIMUM_PRESSURE);
+                }
             }
         }
 
-        for (_, soft_body) in &self.soft_bodies {
-            if bounding_box.is_other_within_distance(&soft_body.bounding_box, 0.2) {
-                soft_body.draw_actors_front();
+        if let Some(selected) = self.input_state.selected_soft_body
+            && let Some(soft_body) = self.soft_bodies.get(selected)
+        {
+            soft_body.outline_color(0.05, colors::BLUE);
+        }
+
+        for &key in &self.input_state.selection {
+            if let Some(soft_body) = self.soft_bodies.get(key)
+                && soft_body.is_visible_in(&view)
+            {
+                soft_body.outline_color(0.05, colors::SKYBLUE);
             }
         }
 
+        if let Some(selection_start) = self.input_state.selection_start {
+            BoundingBox::fit_points(&[selection_start, self.input_state.mouse.position]).draw();
+        }
+
         for (_, soft_body) in &self.soft_bodies {
-            if bounding_box.is_other_within_distance(&soft_body.bounding_box, 0.2) {
-                soft_body.draw_attatchment_points();
+            if soft_body.is_visible_in(&view) {
+                soft_body.draw_actors_front(&self.input_state.suppressed_keys);
+            }
+        }
+
+        for (key, soft_body) in &self.soft_bodies {
+            if soft_body.is_visible_in(&view) {
+                for index in 0..soft_body.attatchment_points.len() {
+                    let color = self
+                        .seam_stress(AttatchmentPointHandle {
+                            soft_body: key,
+                            index,
+                        })
+                        .map(|stress| {
+                            utils::generate_color_for_stress(stress / Self::MAXIMUM_SEAM_STRESS)
+                        });
+
+                    soft_body.draw_attatchment_point(
+                        index,
+                        self.input_state.show_attatchment_points,
+                        color,
+                    );
+
+                    if self.input_state.show_attatchment_points {
+                        soft_body.draw_attatchment_point_length(index);
+                    }
+                }
+            }
+        }
+
+        for (key, soft_body) in &self.soft_bodies {
+            if soft_body.is_visible_in(&view) {
+                for Overlay(overlay) in &self.overlays {
+                    overlay(key, soft_body);
+                }
+            }
+        }
+
+        if self.input_state.solo
+            && let Some(selected) = self.input_state.selected_soft_body
+        {
+            let color = Color {
+                a: Self::SOLO_DIMMING,
+                ..colors::BLACK
+            };
+
+            for (key, soft_body) in &self.soft_bodies {
+                if key != selected && soft_body.is_visible_in(&view) {
+                    soft_body.fill_color(color);
+                    soft_body.outline_color(SoftBody::ATTATCHMENT_POINT_THICKNESS * 2.0, color);
+                }
+            }
+
+            if let Some(soft_body) = self.soft_bodies.get(selected) {
+                soft_body.outline_color(0.05, colors::BLUE);
             }
         }
 
+        if let Some((_, preview)) = &self.input_state.spawn_preview {
+            let color = if self.input_state.spawn_blocked {
+                Self::SPAWN_BLOCKED_COLOR
+            } else {
+                Self::SPAWN_PREVIEW_COLOR
+            };
+
+            preview.fill_color(color);
+            preview.outline_color(0.025, Color { a: 1.0, ..color });
+        }
+
         let color = if self.input_state.target_attatchment_point.is_some()
             && self.input_state.can_connect
         {
@@ -184,21 +886,55 @@ impl Simulation {
             None
         };
 
-        if let Some((attatchment_point, _)) = self.input_state.selected_attatchment_point {
-            if let Some(soft_body) = self.soft_bodies.get(attatchment_point.soft_body) {
-                soft_body.draw_attatchment_point(attatchment_point.index, true, color);
-            }
+        if let Some((attatchment_point, _)) = self.input_state.selected_attatchment_point
+            && let Some(soft_body) = self.soft_bodies.get(attatchment_point.soft_body)
+        {
+            soft_body.draw_attatchment_point(attatchment_point.index, true, color);
+        }
+
+        if let Some(attatchment_point) = self.input_state.target_attatchment_point
+            && let Some(soft_body) = self.soft_bodies.get(attatchment_point.soft_body)
+        {
+            soft_body.draw_attatchment_point(attatchment_point.index, true, color);
         }
 
-        if let Some(attatchment_point) = self.input_state.target_attatchment_point {
-            if let Some(soft_body) = self.soft_bodies.get(attatchment_point.soft_body) {
-                soft_body.draw_attatchment_point(attatchment_point.index, true, color);
+        if self.show_trace {
+            let len = self.trace.len();
+
+            for (i, (&start, &end)) in self.trace.iter().zip(self.trace.iter().skip(1)).enumerate()
+            {
+                utils::draw_line(
+                    start,
+                    end,
+                    Self::TRACE_THICKNESS,
+                    Color {
+                        a: (i + 1) as f32 / len as f32,
+                        ..Self::TRACE_COLOR
+                    },
+                );
             }
         }
 
         if debug {
+            for trigger in &self.triggers {
+                trigger.area.draw();
+            }
+
+            for &(fold, age) in &self.folds {
+                shapes::draw_circle_lines(
+                    fold.x,
+                    fold.y,
+                    0.25,
+                    0.05,
+                    Color {
+                        a: 1.0 - age / Self::FOLD_MARKER_TIME,
+                        ..colors::RED
+                    },
+                );
+            }
+
             for (_, soft_body) in &self.soft_bodies {
-                if soft_body.pressure > f32::EPSILON {
+                if soft_body.pressure > f32::EPSILON && soft_body.is_visible_in(&view) {
                     soft_body.fill_color(Color {
                         a: (soft_body.pressure / soft_body.gas_force).clamp(0.0, 1.0) / 2.0,
                         ..utils::generate_color_for_spring(soft_body.pressure / 3.0, 0.0)
@@ -207,91 +943,998 @@ impl Simulation {
             }
 
             for (_, soft_body) in &self.soft_bodies {
-                if bounding_box.is_other_within_distance(&soft_body.bounding_box, 0.2) {
+                if soft_body.is_visible_in(&view) {
                     soft_body.draw_springs();
                 }
             }
+
+            if self.show_velocities {
+                for (_, soft_body) in &self.soft_bodies {
+                    if soft_body.is_visible_in(&view) {
+                        soft_body.draw_velocities(self.velocity_arrow_scale);
+                    }
+                }
+            }
+
+            for (_, soft_body) in &self.soft_bodies {
+                if soft_body.health.is_finite() && soft_body.is_visible_in(&view) {
+                    utils::draw_world_text(
+                        &format!("{:.1}", soft_body.health),
+                        soft_body.centroid(),
+                        SoftBody::HEALTH_TEXT_SIZE,
+                        colors::WHITE,
+                    );
+                }
+            }
+
+            if let Some(selected) = self.input_state.selected_soft_body
+                && let Some(soft_body) = self.soft_bodies.get(selected)
+            {
+                soft_body.draw_angular_velocity();
+            }
         }
 
         egui_macroquad::draw();
     }
 
-    pub fn update_keys(&mut self) {
-        self.keys = self.soft_bodies.keys().collect();
+    /// Returns every collision since this was last called. Should be called every frame, as the
+    /// events would otherwise build up forever.
+    pub fn drain_collision_events(&mut self) -> std::vec::Drain<'_, CollisionEvent> {
+        self.collision_events.drain(..)
     }
 
-    pub fn tick_simulation(&mut self, dt: f32) -> Option<Vec2> {
-        for particle in &mut self.particles {
-            particle.tick(dt);
-        }
+    /// Moves soft bodies which moved further than [`Self::max_displacement_per_tick`] again in
+    /// smaller steps, checking for collisions with the soft bodies near their path after each.
+    ///
+    /// Only slow soft bodies which aren't connected to the fast one are checked, since the others
+    /// have already moved along with it. Costs one collision check per step per nearby soft body.
+    pub fn substep_fast_soft_bodies(&mut self) {
+        for &key in &self.keys {
+            let soft_body = &self.soft_bodies[key];
 
-        let mut i = 0;
+            let displacement = soft_body.max_displacement();
 
-        while i < self.particles.len() {
-            let particle = &self.particles[i];
-            if particle.age > particle.end_age {
-                self.particles.remove(i);
+            if displacement <= self.max_displacement_per_tick {
                 continue;
             }
 
-            i += 1;
-        }
+            let connected = self.connected_component(key);
 
-        let mut camera_position = None;
+            let swept_area = soft_body.swept_area();
 
-        self.update_grabbing(dt);
+            let neighbors = self
+                .keys
+                .iter()
+                .copied()
+                .filter(|&other_key| {
+                    let other = &self.soft_bodies[other_key];
 
-        let mut unstable_soft_bodies = Vec::new();
+                    swept_area.intersects_other(&other.bounding_box)
+                        && soft_body.collides_with(other)
+                        && !connected.contains(&other_key)
+                        && other.max_displacement() <= self.max_displacement_per_tick
+                })
+                .collect::<Vec<_>>();
 
-        for (i, &key) in self.keys.iter().enumerate() {
-            let soft_body = &mut self.soft_bodies[key];
+            if neighbors.is_empty() {
+                continue;
+            }
 
-            let (new_camera_position, mut new_particles, unstable) =
-                soft_body.apply_impulse_and_velocity(dt);
+            let substeps = ((displacement / self.max_displacement_per_tick).ceil() as usize)
+                .min(Self::MAXIMUM_SUBSTEPS);
 
-            self.particles.append(&mut new_particles);
+            let Some(displacements) = self.soft_bodies[key].rewind() else {
+                continue;
+            };
 
-            if camera_position.is_none() {
-                camera_position = new_camera_position;
-            }
+            for _ in 0..substeps {
+                let soft_body = &mut self.soft_bodies[key];
 
-            if unstable && !soft_body.is_debris() {
-                unstable_soft_bodies.push((i, key));
-            }
-        }
+                for ((point, _), displacement) in soft_body.shape.iter_mut().zip(&displacements) {
+                    point.position += *displacement / substeps as f32;
+                }
 
-        let mut empty_constraints = Vec::new();
+                soft_body.update_bounding_box();
 
-        for (key, constraint) in &mut self.constraints {
-            constraint.apply_to_soft_bodies(&mut self.soft_bodies);
+                for &other_key in &neighbors {
+                    let Some([soft_body, other]) =
+                        self.soft_bodies.get_disjoint_mut([key, other_key])
+                    else {
+                        continue;
+                    };
 
-            if constraint.is_empty() {
-                empty_constraints.push(key);
+                    if !soft_body.bounding_box.intersects_other(&other.bounding_box)
+                        || !soft_body
+                            .bounding_circle()
+                            .intersects_other(&other.bounding_circle())
+                    {
+                        continue;
+                    }
+
+                    // Both directions are checked against the positions from before either, like
+                    // in `tick_simulation`
+                    let states = [Self::point_state(soft_body), Self::point_state(other)];
+                    let mut corrections = states
+                        .each_ref()
+                        .map(|states| vec![(Vec2::ZERO, Vec2::ZERO); states.len()]);
+
+                    let impulse = soft_body
+                        .check_points_against_other_one_sided(other, &mut self.collision_events);
+                    Self::take_corrections(soft_body, &states[0], &mut corrections[0]);
+                    Self::take_corrections(other, &states[1], &mut corrections[1]);
+
+                    let impulse = impulse.max(other.check_points_against_other_one_sided(
+                        soft_body,
+                        &mut self.collision_events,
+                    ));
+                    Self::take_corrections(soft_body, &states[0], &mut corrections[0]);
+                    Self::take_corrections(other, &states[1], &mut corrections[1]);
+
+                    Self::apply_corrections(soft_body, &corrections[0]);
+                    Self::apply_corrections(other, &corrections[1]);
+
+                    soft_body.take_damage(impulse);
+                    other.take_damage(impulse);
+                }
             }
         }
+    }
 
-        for key in empty_constraints {
-            self.remove_constraint(key, None);
+    /// The position and velocity of every point of every soft body, in the order of [`Self::keys`]
+    fn point_states(&self) -> Vec<Vec<(Vec2, Vec2)>> {
+        self.keys
+            .iter()
+            .map(|&key| Self::point_state(&self.soft_bodies[key]))
+            .collect()
+    }
+
+    /// The position and velocity of every point of `soft_body`
+    fn point_state(soft_body: &SoftBody) -> Vec<(Vec2, Vec2)> {
+        soft_body
+            .shape
+            .iter()
+            .map(|(point, _)| (point.position, point.velocity))
+            .collect()
+    }
+
+    /// Adds how far each point of `soft_body` has moved and accelerated since `states` to
+    /// `corrections`, and puts the point back the way it was
+    fn take_corrections(
+        soft_body: &mut SoftBody,
+        states: &[(Vec2, Vec2)],
+        corrections: &mut [(Vec2, Vec2)],
+    ) {
+        for (((point, _), &(position, velocity)), correction) in
+            soft_body.shape.iter_mut().zip(states).zip(corrections)
+        {
+            correction.0 += point.position - position;
+            correction.1 += point.velocity - velocity;
+
+            point.position = position;
+            point.velocity = velocity;
+        }
+    }
+
+    /// Moves and accelerates each point of `soft_body` by the corrections gathered with
+    /// [`Self::take_corrections`]
+    fn apply_corrections(soft_body: &mut SoftBody, corrections: &[(Vec2, Vec2)]) {
+        for ((point, _), &(position, velocity)) in soft_body.shape.iter_mut().zip(corrections) {
+            point.position += position;
+            point.velocity += velocity;
+        }
+    }
+
+    /// Returns every time a soft body entered or exited a trigger since this was last called.
+    pub fn drain_trigger_events(&mut self) -> std::vec::Drain<'_, TriggerEvent> {
+        self.trigger_events.drain(..)
+    }
+
+    /// Completes the objective the first time [`Self::win_condition`] is met
+    pub fn update_objective(&mut self) {
+        if !self.objective_complete
+            && let Some(win_condition) = self.win_condition
+            && win_condition.is_met(self)
+        {
+            self.objective_complete = true;
+            self.win_event = true;
+        }
+    }
+
+    /// Returns `true` once after the objective is completed
+    pub fn take_win_event(&mut self) -> bool {
+        mem::take(&mut self.win_event)
+    }
+
+    /// Gives off sparks from each habitat bubble which entered a refuel zone in the trigger events
+    /// from `first_event` onwards. Refuel zones refuel whatever is inside them every tick, so their
+    /// events are only used for this, and are left for [`Self::drain_trigger_events`].
+    pub fn emit_refuel_particles(&mut self, first_event: usize) {
+        let entered = self.trigger_events[first_event..]
+            .iter()
+            .filter_map(|event| match *event {
+                TriggerEvent::Enter { trigger, soft_body }
+                    if self
+                        .triggers
+                        .get(trigger)
+                        .is_some_and(|trigger| trigger.refuel_rate > 0.0) =>
+                {
+                    Some(soft_body)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for key in entered {
+            let Some(soft_body) = self.soft_bodies.get(key) else {
+                continue;
+            };
+
+            if soft_body
+                .actors
+                .iter()
+                .any(|actor| matches!(actor, Actor::HabitatBubble { .. }))
+            {
+                let positions = soft_body
+                    .shape
+                    .iter()
+                    .map(|(point, _)| point.position)
+                    .collect::<Vec<_>>();
+                let velocity = soft_body.velocity();
+
+                self.emit_particles(&positions, velocity, Self::REFUEL_PARTICLE_COLOR, 2.0);
+            }
+        }
+    }
+
+    pub fn update_triggers(&mut self) {
+        for (i, trigger) in self.triggers.iter_mut().enumerate() {
+            let soft_bodies = &self.soft_bodies;
+            let area = &trigger.area;
+
+            trigger.bodies_inside.retain(|&key| {
+                let inside = soft_bodies
+                    .get(key)
+                    .is_some_and(|soft_body| Trigger::area_contains(area, soft_body));
+
+                if !inside {
+                    self.trigger_events.push(TriggerEvent::Exit {
+                        trigger: i,
+                        soft_body: key,
+                    });
+                }
+
+                inside
+            });
+
+            for &key in &self.keys {
+                if !trigger.bodies_inside.contains(&key) && trigger.contains(&soft_bodies[key]) {
+                    trigger.bodies_inside.push(key);
+
+                    self.trigger_events.push(TriggerEvent::Enter {
+                        trigger: i,
+                        soft_body: key,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Bounces or wraps the soft bodies outside [`Self::world_boundary`]. Wrapping moves whole
+    /// connected ships at once, so they aren't torn apart by their constraints.
+    pub fn apply_world_boundary(&mut self) {
+        let Some(WorldBoundary { area, mode }) = self.world_boundary else {
+            return;
+        };
+
+        let max_corner = area.max_corner();
+
+        match mode {
+            BoundaryMode::Bounce => {
+                for &key in &self.keys {
+                    for (point, _) in &mut self.soft_bodies[key].shape {
+                        for axis in 0..2 {
+                            if point.position[axis] < area.min_corner[axis] {
+                                point.position[axis] = area.min_corner[axis];
+                                point.velocity[axis] = point.velocity[axis].abs();
+                            } else if point.position[axis] > max_corner[axis] {
+                                point.position[axis] = max_corner[axis];
+                                point.velocity[axis] = -point.velocity[axis].abs();
+                            }
+                        }
+                    }
+                }
+            }
+            BoundaryMode::Wrap => {
+                let mut visited = HashSet::new();
+
+                for i in 0..self.keys.len() {
+                    let key = self.keys[i];
+
+                    if visited.contains(&key) {
+                        continue;
+                    }
+
+                    let component = self.connected_component(key);
+                    visited.extend(component.iter().copied());
+
+                    let total_mass = component
+                        .iter()
+                        .map(|&key| self.soft_bodies[key].total_mass())
+                        .sum::<f32>();
+                    let center_of_mass = component
+                        .iter()
+                        .map(|&key| {
+                            let soft_body = &self.soft_bodies[key];
+                            soft_body.center_of_mass() * soft_body.total_mass()
+                        })
+                        .sum::<Vec2>()
+                        / total_mass;
+
+                    let mut offset = Vec2::ZERO;
+
+                    for axis in 0..2 {
+                        if center_of_mass[axis] < area.min_corner[axis] {
+                            offset[axis] = area.size[axis];
+                        } else if center_of_mass[axis] > max_corner[axis] {
+                            offset[axis] = -area.size[axis];
+                        }
+                    }
+
+                    if offset != Vec2::ZERO {
+                        for &key in &component {
+                            self.soft_bodies[key].add_offset(Vector2::new(offset.x, offset.y));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Refuels the habitat bubbles inside refuel zones, then takes the fuel used by the rocket
+    /// motors of each ship from the habitat bubbles in it. Once a ship's fuel runs out, its rocket
+    /// motors stop until it is refueled.
+    pub fn update_fuel(&mut self, dt: f32) {
+        for trigger in &self.triggers {
+            if trigger.refuel_rate <= 0.0 {
+                continue;
+            }
+
+            for &key in &trigger.bodies_inside {
+                let Some(soft_body) = self.soft_bodies.get_mut(key) else {
+                    continue;
+                };
+
+                for actor in &mut soft_body.actors {
+                    if let Actor::HabitatBubble {
+                        fuel,
+                        fuel_capacity,
+                        ..
+                    } = actor
+                    {
+                        *fuel = (*fuel + trigger.refuel_rate * dt).min(*fuel_capacity);
+                    }
+                }
+            }
+        }
+
+        let mut visited = HashSet::new();
+
+        for i in 0..self.keys.len() {
+            let key = self.keys[i];
+
+            if visited.contains(&key)
+                || self.soft_bodies[key].connection_state != ConnectionState::Source
+            {
+                continue;
+            }
+
+            let component = self.connected_component(key);
+            visited.extend(component.iter().copied());
+
+            let mut demand = component
+                .iter()
+                .flat_map(|&key| &self.soft_bodies[key].actors)
+                .map(|actor| match actor {
+                    Actor::RocketMotor { force, enable, .. }
+                        if enable.is_down(&self.input_state.suppressed_keys) =>
+                    {
+                        force.length() * dt * Self::FUEL_PER_IMPULSE
+                    }
+                    _ => 0.0,
+                })
+                .sum::<f32>();
+
+            let mut fuel_left = 0.0;
+
+            for &key in &component {
+                for actor in &mut self.soft_bodies[key].actors {
+                    if let Actor::HabitatBubble { fuel, .. } = actor {
+                        let used = demand.min(*fuel);
+
+                        *fuel -= used;
+                        demand -= used;
+                        fuel_left += *fuel;
+                    }
+                }
+            }
+
+            for &key in &component {
+                self.soft_bodies[key].out_of_fuel = fuel_left <= 0.0;
+            }
+        }
+    }
+
+    /// Fires the rocket motors and reaction wheels of the ship containing `ship_root` to cancel out
+    /// its velocity and spin, ignoring their keybinds. Each is only used up to its own force or
+    /// torque, and rocket motors stop once the ship is out of fuel.
+    pub fn autopilot_hold(&mut self, ship_root: SoftBodyKey, dt: f32) {
+        let component = self.connected_component(ship_root);
+
+        let mut mass = 0.0;
+        let mut mass_moment = Vec2::ZERO;
+        let mut momentum = Vec2::ZERO;
+
+        for &key in &component {
+            for (point, _) in &self.soft_bodies[key].shape {
+                mass += point.mass;
+                mass_moment += point.position * point.mass;
+                momentum += point.velocity * point.mass;
+            }
+        }
+
+        if mass <= f32::EPSILON {
+            return;
+        }
+
+        let center_of_mass = mass_moment / mass;
+        let velocity = momentum / mass;
+
+        let mut angular_momentum = 0.0;
+
+        for &key in &component {
+            for (point, _) in &self.soft_bodies[key].shape {
+                let offset = point.position - center_of_mass;
+
+                angular_momentum += offset.perp_dot(point.velocity - velocity) * point.mass;
+            }
+        }
+
+        let total_torque = component
+            .iter()
+            .flat_map(|&key| &self.soft_bodies[key].actors)
+            .map(|actor| match actor {
+                Actor::ReactionWheel { torque, .. } => *torque,
+                _ => 0.0,
+            })
+            .sum::<f32>();
+
+        // The share of every reaction wheel's torque needed to stop the spin within this tick
+        let wheel_throttle = if total_torque > f32::EPSILON {
+            (-angular_momentum / (total_torque * dt)).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let num_motors = component
+            .iter()
+            .flat_map(|&key| &self.soft_bodies[key].actors)
+            .filter(|actor| matches!(actor, Actor::RocketMotor { .. }))
+            .count();
+
+        let mut fuel_used = 0.0;
+
+        for &key in &component {
+            let soft_body = &mut self.soft_bodies[key];
+
+            if !soft_body.connection_state.is_connected() {
+                continue;
+            }
+
+            let thrust_available = soft_body.thrust_available();
+            let mut wheel_torque = 0.0;
+
+            for actor in &soft_body.actors {
+                match actor {
+                    Actor::RocketMotor {
+                        line,
+                        force,
+                        gimbal,
+                        ..
+                    } if thrust_available => {
+                        let next = soft_body.next_point(*line);
+
+                        let [(point_a, _), (point_b, _)] =
+                            soft_body.shape.get_disjoint_mut([*line, next]).unwrap();
+
+                        let gimbal_angle = gimbal.as_ref().map_or(0.0, |gimbal| gimbal.angle);
+                        let force = (point_b.position - point_a.position)
+                            .perp()
+                            .normalize_or_zero()
+                            .rotate(Vec2::from_angle(gimbal_angle).rotate(*force));
+
+                        if force.length_squared() <= f32::EPSILON {
+                            continue;
+                        }
+
+                        // Each motor takes an even share of the impulse needed along its thrust
+                        let throttle = (-momentum.dot(force.normalize())
+                            / (force.length() * dt * num_motors as f32))
+                            .clamp(0.0, 1.0);
+
+                        point_a.impulse += force * throttle / 2.0 * dt;
+                        point_b.impulse += force * throttle / 2.0 * dt;
+
+                        fuel_used += force.length() * throttle * dt * Self::FUEL_PER_IMPULSE;
+                    }
+                    Actor::ReactionWheel { torque, .. } => {
+                        wheel_torque += wheel_throttle * *torque;
+                    }
+                    _ => (),
+                }
+            }
+
+            if wheel_torque != 0.0 {
+                soft_body.apply_torque(wheel_torque, dt);
+            }
+        }
+
+        for &key in &component {
+            for actor in &mut self.soft_bodies[key].actors {
+                if let Actor::HabitatBubble { fuel, .. } = actor {
+                    let used = fuel_used.min(*fuel);
+
+                    *fuel -= used;
+                    fuel_used -= used;
+                }
+            }
+        }
+    }
+
+    /// The first pressurized habitat bubble, which the camera follows
+    pub fn followed_habitat_bubble(&self) -> Option<SoftBodyKey> {
+        self.keys.iter().copied().find(|&key| {
+            let soft_body = &self.soft_bodies[key];
+
+            soft_body.actors.iter().any(|actor| {
+                matches!(actor, Actor::HabitatBubble { minimum_pressure, .. }
+                    if soft_body.pressure > *minimum_pressure)
+            })
+        })
+    }
+
+    /// Moves everything so the followed habitat bubble is at the origin once it is further than
+    /// [`Self::RECENTER_DISTANCE`] from it, since positions far from the origin lose precision.
+    /// Returns the offset, which anything outside of the simulation such as the camera and the star
+    /// field must also be moved by.
+    pub fn recenter(&mut self) -> Option<Vec2> {
+        let center = self.soft_bodies[self.followed_habitat_bubble()?].center_of_mass();
+
+        if center.length() < Self::RECENTER_DISTANCE {
+            return None;
+        }
+
+        self.add_offset(Vector2::new(-center.x, -center.y));
+
+        Some(-center)
+    }
+
+    /// The thrust, mass, and fuel of the [connected component](Self::connected_component) of
+    /// `key`
+    pub fn ship_stats(&self, key: SoftBodyKey) -> ShipStats {
+        let component = self.connected_component(key);
+
+        let mass = component
+            .iter()
+            .map(|&key| self.soft_bodies[key].total_mass())
+            .sum::<f32>();
+
+        let mut thrust = 0.0;
+        let mut fuel = None;
+
+        for actor in component
+            .iter()
+            .flat_map(|&key| &self.soft_bodies[key].actors)
+        {
+            match actor {
+                Actor::RocketMotor { force, .. } => thrust += force.length(),
+                Actor::HabitatBubble { fuel: amount, .. } => {
+                    *fuel.get_or_insert(0.0) += amount;
+                }
+                _ => (),
+            }
+        }
+
+        ShipStats {
+            thrust,
+            mass,
+            max_acceleration: thrust / mass,
+            delta_v: fuel.map(|fuel| fuel / Self::FUEL_PER_IMPULSE / mass),
+        }
+    }
+
+    /// The fuel left in the ship the camera is following, and how much it can hold
+    pub fn followed_ship_fuel(&self) -> Option<(f32, f32)> {
+        let key = self.followed_habitat_bubble()?;
+
+        Some(
+            self.connected_component(key)
+                .iter()
+                .flat_map(|&key| &self.soft_bodies[key].actors)
+                .fold((0.0, 0.0), |(total, total_capacity), actor| match actor {
+                    Actor::HabitatBubble {
+                        fuel,
+                        fuel_capacity,
+                        ..
+                    } => (total + fuel, total_capacity + fuel_capacity),
+                    _ => (total, total_capacity),
+                }),
+        )
+    }
+
+    /// Removes every soft body, constraint, and particle. The input state is reset first and the
+    /// constraints are removed before the soft bodies, so that nothing is left holding a handle to
+    /// a removed soft body.
+    pub fn clear(&mut self) {
+        self.input_state = InputState::default();
+
+        self.constraints.clear();
+        self.soft_bodies.clear();
+        self.keys.clear();
+
+        self.particles.clear();
+        self.collision_events.clear();
+        self.folds.clear();
+        self.trace.clear();
+        self.trace_body = None;
+
+        for trigger in &mut self.triggers {
+            trigger.bodies_inside.clear();
+        }
+        self.trigger_events.clear();
+
+        self.win_condition = None;
+        self.objective_complete = false;
+        self.win_event = false;
+    }
+
+    /// Replaces the scene and its triggers with the ones from [`crate::assemble_simulation`],
+    /// keeping the controls.
+    pub fn reset_to_default(&mut self) {
+        self.clear();
+
+        let default = crate::assemble_simulation();
+
+        self.soft_bodies = default.soft_bodies;
+        self.keys = default.keys;
+        self.constraints = default.constraints;
+        self.triggers = default.triggers;
+    }
+
+    /// Moves everything in `other` by `offset` and adds it to this simulation, keeping the
+    /// connections between its soft bodies. Its soft bodies and constraints are given new keys,
+    /// which are returned as a map from their old keys.
+    pub fn import(
+        &mut self,
+        mut other: Simulation,
+        offset: Vec2,
+    ) -> HashMap<SoftBodyKey, SoftBodyKey> {
+        other.add_offset(Vector2::new(offset.x, offset.y));
+
+        let soft_body_keys = other
+            .soft_bodies
+            .drain()
+            .map(|(key, soft_body)| (key, self.soft_bodies.insert(soft_body)))
+            .collect::<HashMap<_, _>>();
+
+        let mut constraint_keys = HashMap::new();
+
+        for (key, mut constraint) in other.constraints.drain() {
+            match &mut constraint {
+                Constraint::HoldTogether { points, .. } => {
+                    points.retain_mut(|point| {
+                        let Some(&soft_body) = soft_body_keys.get(&point.soft_body) else {
+                            return false;
+                        };
+
+                        point.soft_body = soft_body;
+                        true
+                    });
+                }
+            }
+
+            constraint_keys.insert(key, self.constraints.insert(constraint));
+        }
+
+        for &key in soft_body_keys.values() {
+            let soft_body = &mut self.soft_bodies[key];
+
+            for (point, _) in &mut soft_body.shape {
+                point.constraint = point
+                    .constraint
+                    .and_then(|key| constraint_keys.get(&key).copied());
+            }
+
+            for attatchment_point in &mut soft_body.attatchment_points {
+                attatchment_point.connections.retain_mut(|connection| {
+                    let Some(&new_key) = soft_body_keys.get(&connection.soft_body) else {
+                        return false;
+                    };
+
+                    connection.soft_body = new_key;
+                    true
+                });
+            }
+        }
+
+        for trigger in &mut other.triggers {
+            trigger.bodies_inside.retain_mut(|key| {
+                let Some(&new_key) = soft_body_keys.get(key) else {
+                    return false;
+                };
+
+                *key = new_key;
+                true
+            });
+        }
+
+        self.particles.append(&mut other.particles);
+        self.triggers.append(&mut other.triggers);
+
+        self.update_keys();
+
+        soft_body_keys
+    }
+
+    /// Builds the prefab centered on `position`
+    pub fn spawn_prefab(&mut self, prefab: Prefab, position: Vec2) -> SoftBodyKey {
+        let key = self
+            .soft_bodies
+            .insert(Self::build_prefab(prefab, position));
+        self.update_keys();
+
+        key
+    }
+
+    /// Builds the prefab centered on `position` without adding it to the simulation
+    pub fn build_prefab(prefab: Prefab, position: Vec2) -> SoftBody {
+        let mut soft_body = prefab(Vec2::ZERO).build();
+        soft_body.translate(position - soft_body.centroid());
+        soft_body
+    }
+
+    /// Moves the preview of the prefab being spawned to `position`, building it first if the
+    /// selected prefab changed, and checks whether it overlaps anything
+    fn update_spawn_preview(&mut self, position: Vec2) {
+        let Some(index) = self.input_state.spawning else {
+            self.input_state.spawn_preview = None;
+            self.input_state.spawn_blocked = false;
+            return;
+        };
+
+        match &mut self.input_state.spawn_preview {
+            Some((preview_index, preview)) if *preview_index == index => {
+                preview.translate(position - preview.centroid());
+            }
+            spawn_preview => {
+                *spawn_preview = Some((
+                    index,
+                    Self::build_prefab(prefabs::PALETTE[index].1, position),
+                ));
+            }
+        }
+
+        let (_, preview) = self.input_state.spawn_preview.as_ref().unwrap();
+
+        self.input_state.spawn_blocked = self
+            .soft_bodies
+            .values()
+            .any(|soft_body| soft_body.debris_age.is_none() && soft_body.overlaps(preview));
+    }
+
+    pub fn update_keys(&mut self) {
+        self.keys = self.soft_bodies.keys().collect();
+    }
+
+    /// Captures the positions and velocities of every point, the pressure of every soft body,
+    /// and the particles. Much cheaper than cloning the whole simulation.
+    pub fn snapshot(&self) -> SimulationSnapshot {
+        SimulationSnapshot {
+            soft_bodies: self
+                .soft_bodies
+                .iter()
+                .map(|(key, soft_body)| (key, soft_body.snapshot()))
+                .collect(),
+            particles: self.particles.clone(),
+        }
+    }
+
+    /// Writes a snapshot back without reallocating any soft bodies. Soft bodies which were
+    /// removed or changed shape since the snapshot was taken are skipped, and soft bodies which
+    /// were added since are left as they are.
+    pub fn restore(&mut self, snapshot: &SimulationSnapshot) {
+        for (key, soft_body_snapshot) in &snapshot.soft_bodies {
+            if let Some(soft_body) = self.soft_bodies.get_mut(*key) {
+                let _ = soft_body.restore(soft_body_snapshot);
+            }
+        }
+
+        self.particles.clear();
+        self.particles.extend_from_slice(&snapshot.particles);
+    }
+
+    pub fn tick_simulation(&mut self, dt: f32) -> Option<Vec2> {
+        let mut timer = Instant::now();
+
+        for particle in &mut self.particles {
+            particle.tick(dt);
+        }
+
+        let mut i = 0;
+
+        while i < self.particles.len() {
+            let particle = &self.particles[i];
+            if particle.age > particle.end_age {
+                self.particles.remove(i);
+                continue;
+            }
+
+            i += 1;
+        }
+
+        for (_, age) in &mut self.folds {
+            *age += dt;
+        }
+
+        self.folds.retain(|&(_, age)| age < Self::FOLD_MARKER_TIME);
+
+        PhaseTimings::lap(&mut self.phase_timings.cleanup, &mut timer);
+
+        let mut camera_position = None;
+
+        self.update_grabbing(dt);
+        self.update_fuel(dt);
+
+        if self.input_state.autopilot
+            && let Some(key) = self.followed_habitat_bubble()
+        {
+            self.autopilot_hold(key, dt);
+        }
+
+        let mut unstable_soft_bodies = Vec::new();
+
+        for &key in &self.keys {
+            let soft_body = &mut self.soft_bodies[key];
+
+            let (new_camera_position, mut new_particles, unstable) = soft_body
+                .apply_impulse_and_velocity(dt, self.integrator, &self.input_state.suppressed_keys);
+
+            self.particles.append(&mut new_particles);
+
+            if camera_position.is_none() {
+                camera_position = new_camera_position;
+            }
+
+            if unstable && !soft_body.is_debris() {
+                unstable_soft_bodies.push(key);
+            }
+        }
+
+        PhaseTimings::lap(&mut self.phase_timings.forces, &mut timer);
+
+        let mut empty_constraints = Vec::new();
+
+        for (key, constraint) in &mut self.constraints {
+            constraint.apply_to_soft_bodies(&mut self.soft_bodies);
+
+            if constraint.is_empty() {
+                empty_constraints.push(key);
+            }
+        }
+
+        for key in empty_constraints {
+            self.remove_constraint(key, None);
+        }
+
+        PhaseTimings::lap(&mut self.phase_timings.constraints, &mut timer);
+
+        self.substep_fast_soft_bodies();
+
+        PhaseTimings::lap(&mut self.phase_timings.substeps, &mut timer);
+
+        let swept_areas = (self.continuous_collision).then(|| {
+            self.keys
+                .iter()
+                .map(|&key| self.soft_bodies[key].swept_area())
+                .collect::<Vec<_>>()
+        });
+
+        let mut pairs = Vec::new();
+
+        for (i, &first_key) in self.keys.iter().enumerate().skip(1) {
+            for (j, &second_key) in self.keys.iter().enumerate().take(i) {
+                let (first, second) = (&self.soft_bodies[first_key], &self.soft_bodies[second_key]);
+
+                if !first.collides_with(second) || first.frozen && second.frozen {
+                    continue;
+                }
+
+                let overlapping = first.bounding_box.intersects_other(&second.bounding_box)
+                    && first
+                        .bounding_circle()
+                        .intersects_other(&second.bounding_circle());
+                let swept = swept_areas
+                    .as_ref()
+                    .is_some_and(|swept_areas| swept_areas[i].intersects_other(&swept_areas[j]));
+
+                if overlapping || swept {
+                    pairs.push((i, j, overlapping, swept));
+                }
+            }
         }
 
-        for (i, &first_key) in self.keys.iter().enumerate().skip(1) {
-            for &second_key in self.keys.iter().take(i) {
-                let [first, second] = self
+        PhaseTimings::lap(&mut self.phase_timings.broad_phase, &mut timer);
+
+        // Every collision is resolved against the points as they were before any collisions, and
+        // the corrections are applied together afterwards, so the order of `self.keys` doesn't
+        // favor any soft body
+        let states = self.point_states();
+        let mut corrections = states
+            .iter()
+            .map(|states| vec![(Vec2::ZERO, Vec2::ZERO); states.len()])
+            .collect::<Vec<_>>();
+
+        // The strongest impulse between each pair of soft bodies, so that a collision found from
+        // both sides only damages each of them once
+        let mut pair_impulses = Vec::new();
+
+        for (i, j, overlapping, swept) in pairs {
+            let mut impulse = 0.0_f32;
+
+            for [a, b] in [[i, j], [j, i]] {
+                let Some([soft_body, other]) = self
                     .soft_bodies
-                    .get_disjoint_mut([first_key, second_key])
-                    .unwrap();
+                    .get_disjoint_mut([self.keys[a], self.keys[b]])
+                else {
+                    continue;
+                };
+
+                if overlapping {
+                    impulse =
+                        impulse.max(soft_body.check_points_against_other_one_sided(
+                            other,
+                            &mut self.collision_events,
+                        ));
+                }
 
-                if first.bounding_box.intersects_other(&second.bounding_box) {
-                    first.check_points_against_other_one_sided(second);
-                    second.check_points_against_other_one_sided(first);
+                if swept {
+                    impulse = impulse.max(
+                        soft_body
+                            .check_swept_points_against_other(other, &mut self.collision_events),
+                    );
                 }
+
+                Self::take_corrections(soft_body, &states[a], &mut corrections[a]);
+                Self::take_corrections(other, &states[b], &mut corrections[b]);
+            }
+
+            pair_impulses.push((i, j, impulse));
+        }
+
+        for (i, j, impulse) in pair_impulses {
+            for key in [self.keys[i], self.keys[j]] {
+                self.soft_bodies[key].take_damage(impulse);
             }
         }
 
-        for (i, key) in unstable_soft_bodies {
-            self.destroy_soft_body(key, Some(i));
+        for (&key, corrections) in self.keys.iter().zip(&corrections) {
+            Self::apply_corrections(&mut self.soft_bodies[key], corrections);
         }
 
+        PhaseTimings::lap(&mut self.phase_timings.narrow_phase, &mut timer);
+
+        self.destroy_soft_bodies(&unstable_soft_bodies);
+
+        let mut destroyed_soft_bodies = Vec::new();
         let mut i = 0;
 
         while i < self.keys.len() {
@@ -307,11 +1950,27 @@ impl Simulation {
                     continue;
                 }
             } else {
-                if soft_body.is_self_intersecting() {
-                    self.destroy_soft_body(key, Some(i));
+                if soft_body.health <= 0.0 {
+                    destroyed_soft_bodies.push(key);
+                    i += 1;
 
                     continue;
                 }
+
+                if let Some((fold, edges)) = soft_body.self_intersection() {
+                    if self.fold_recovery && soft_body.folded_ticks < Self::MAXIMUM_FOLDED_TICKS {
+                        soft_body.folded_ticks += 1;
+                        soft_body.push_fold_apart(edges, dt);
+                    } else {
+                        self.folds.push((fold, 0.0));
+                        destroyed_soft_bodies.push(key);
+                        i += 1;
+
+                        continue;
+                    }
+                } else {
+                    soft_body.folded_ticks = 0;
+                }
             }
 
             soft_body.update_triangulation_indecies();
@@ -319,6 +1978,20 @@ impl Simulation {
             i += 1;
         }
 
+        self.destroy_soft_bodies(&destroyed_soft_bodies);
+        self.enforce_body_budget();
+
+        self.apply_world_boundary();
+
+        let first_trigger_event = self.trigger_events.len();
+        self.update_triggers();
+        self.emit_refuel_particles(first_trigger_event);
+        self.update_objective();
+        self.update_trace();
+
+        PhaseTimings::lap(&mut self.phase_timings.cleanup, &mut timer);
+        self.phase_timings.ticks += 1;
+
         self.input_state.clicking = false;
 
         if camera_position.is_none() && self.input_state.following_camera {
@@ -334,6 +2007,11 @@ impl Simulation {
     pub fn update_input(&mut self, camera: &Camera2D, dt: f32) {
         const SELECTION_RANGE: f32 = 0.25;
 
+        // Whatever key the editor captures this frame shouldn't also reach the actors
+        let last_key_pressed = input::get_last_key_pressed();
+        self.input_state
+            .update_suppressed_keys(last_key_pressed, input::is_key_down);
+
         self.update_gui();
 
         let mouse_position = utils::mouse_position(camera);
@@ -351,6 +2029,21 @@ impl Simulation {
             self.input_state.grabbing = false;
         }
 
+        self.update_spawn_preview(mouse_position);
+
+        if self.input_state.clicking && self.input_state.spawning.is_some() {
+            if !self.input_state.spawn_blocked {
+                let (_, preview) = self.input_state.spawn_preview.take().unwrap();
+
+                self.soft_bodies.insert(preview);
+                self.update_keys();
+
+                self.input_state.spawning = None;
+            }
+
+            self.input_state.clicking = false;
+        }
+
         let mut selected_attatchment_point = None;
         let mut selected_distance_squared = f32::INFINITY;
 
@@ -372,26 +2065,32 @@ impl Simulation {
                     )
                 })
                 .flatten()
-                .flatten();
+                .flatten()
+                .or(self.input_state.seam_length_filter);
 
             for (key, soft_body) in &self.soft_bodies {
                 if !soft_body
                     .bounding_box
                     .is_point_within_distance(mouse_position, 0.25)
+                    || !soft_body
+                        .bounding_circle()
+                        .is_point_within_distance(mouse_position, 0.25)
                     || Some(key) == key_to_skip
                 {
                     continue;
                 }
 
                 for (index, attatchment_point) in soft_body.attatchment_points.iter().enumerate() {
-                    if attatchment_point.connection.is_some() && self.input_state.grabbing {
+                    if attatchment_point.connections.len() >= Self::MAX_SEAM_CONNECTIONS
+                        && self.input_state.grabbing
+                    {
                         continue;
                     }
 
-                    if let Some(required_length) = required_length {
-                        if required_length != attatchment_point.length {
-                            continue;
-                        }
+                    if let Some(required_length) = required_length
+                        && required_length != attatchment_point.length
+                    {
+                        continue;
                     }
 
                     let mut i = attatchment_point.start_point;
@@ -465,15 +2164,15 @@ impl Simulation {
         }
 
         self.input_state.can_connect = false;
-        if let Some(target) = self.input_state.target_attatchment_point {
-            if let Some((selected, _)) = self.input_state.selected_attatchment_point {
-                self.input_state.can_connect = self
-                    .are_attatchment_points_within_range(
-                        [selected, target],
-                        Self::MAXIMUM_ATTATCHMENT_DISTANCE,
-                    )
-                    .unwrap_or(false);
-            }
+        if let Some(target) = self.input_state.target_attatchment_point
+            && let Some((selected, _)) = self.input_state.selected_attatchment_point
+        {
+            self.input_state.can_connect = self
+                .are_attatchment_points_within_range(
+                    [selected, target],
+                    Self::MAXIMUM_ATTATCHMENT_DISTANCE,
+                )
+                .unwrap_or(false);
         }
 
         if self.input_state.grabbing {
@@ -481,18 +2180,24 @@ impl Simulation {
                 self.input_state.target_attatchment_point =
                     selected_attatchment_point.map(|(handle, _)| handle);
             }
+
+            if input::is_key_pressed(self.controls.align)
+                && let Some((selected, _)) = self.input_state.selected_attatchment_point
+                && let Some(target) = self.input_state.target_attatchment_point
+            {
+                let _ = self.align_attatchment_points([selected, target]);
+            }
         } else {
             if let Some(target) = self.input_state.target_attatchment_point {
-                if let Some((selected, _)) = self.input_state.selected_attatchment_point {
-                    if self
+                if let Some((selected, _)) = self.input_state.selected_attatchment_point
+                    && self
                         .are_attatchment_points_within_range(
                             [selected, target],
                             Self::MAXIMUM_ATTATCHMENT_DISTANCE,
                         )
                         .unwrap()
-                    {
-                        self.connect_attatchment_points([selected, target]).unwrap();
-                    }
+                {
+                    self.connect_attatchment_points([selected, target]).unwrap();
                 }
 
                 self.input_state.target_attatchment_point = None;
@@ -501,6 +2206,25 @@ impl Simulation {
             self.input_state.selected_attatchment_point = selected_attatchment_point;
         }
 
+        if !self.input_state.grabbing {
+            self.input_state.grabbed_line = None;
+        } else if self.input_state.clicking
+            && !self.input_state.ui_hovered
+            && self.input_state.selected_attatchment_point.is_none()
+        {
+            self.input_state.grabbed_line = self
+                .soft_bodies
+                .iter()
+                .find(|(_, soft_body)| {
+                    soft_body.debris_age.is_none() && soft_body.contains_point(mouse_position)
+                })
+                .map(|(key, soft_body)| {
+                    let (line, _, _, progress) = soft_body.closest_line_to_point(mouse_position);
+
+                    (key, line, progress)
+                });
+        }
+
         if !self.input_state.ui_hovered {
             if input::is_mouse_button_pressed(MouseButton::Left)
                 && self.input_state.selected_soft_body.is_some()
@@ -511,7 +2235,7 @@ impl Simulation {
             if input::is_mouse_button_pressed(MouseButton::Right) {
                 'outer: {
                     for (key, soft_body) in &self.soft_bodies {
-                        if soft_body.uses_keybinds() && soft_body.contains_point(mouse_position) {
+                        if soft_body.contains_point(mouse_position) {
                             self.input_state.editing = true;
                             self.input_state.selected_soft_body = Some(key);
                             break 'outer;
@@ -519,15 +2243,66 @@ impl Simulation {
                     }
 
                     self.input_state.editing = false;
+                    self.input_state.selection_start = Some(mouse_position);
                 }
             }
         }
 
+        if let Some(selection_start) = self.input_state.selection_start
+            && !input::is_mouse_button_down(MouseButton::Right)
+        {
+            self.input_state.selection_start = None;
+
+            let area = BoundingBox::fit_points(&[selection_start, mouse_position]);
+
+            self.input_state.selection = self
+                .soft_bodies
+                .iter()
+                .filter(|(_, soft_body)| {
+                    soft_body.uses_keybinds() && area.contains_point(soft_body.centroid())
+                })
+                .map(|(key, _)| key)
+                .collect();
+        }
+
         if !self.input_state.editing {
             self.input_state.selected_soft_body = None;
         }
 
-        if input::is_key_pressed(KeyCode::F1) {
+        self.update_nudging();
+        self.update_clipboard();
+
+        if self.input_state.is_control_pressed(self.controls.solo) {
+            self.input_state.solo ^= true;
+        }
+
+        if self
+            .input_state
+            .is_control_pressed(self.controls.attatchment_points)
+        {
+            self.input_state.show_attatchment_points ^= true;
+        }
+
+        if self.input_state.is_control_pressed(self.controls.trace) {
+            self.show_trace ^= true;
+        }
+
+        if self.input_state.is_control_pressed(self.controls.freeze)
+            && let Some(soft_body) = self
+                .input_state
+                .selected_soft_body
+                .and_then(|key| self.soft_bodies.get_mut(key))
+        {
+            soft_body.frozen ^= true;
+        }
+
+        if input::is_key_pressed(self.controls.autopilot)
+            && self.input_state.controls_focus.is_none()
+        {
+            self.input_state.autopilot ^= true;
+        }
+
+        if input::is_key_pressed(self.controls.menu) && self.input_state.controls_focus.is_none() {
             if self.input_state.editing && self.input_state.selected_soft_body.is_none() {
                 self.input_state.editing = false;
             } else {
@@ -543,42 +2318,275 @@ impl Simulation {
             && (input::is_key_down(KeyCode::LeftControl)
                 || input::is_key_down(KeyCode::RightControl))
         {
-            let mut i = 0;
-
-            while i < self.keys.len() {
-                let key = self.keys[i];
-
-                if self.soft_bodies[key]
-                    .actors
-                    .iter()
-                    .any(|actor| matches!(actor, Actor::HabitatBubble { .. }))
-                {
-                    self.destroy_soft_body(key, Some(i));
-                    continue;
-                }
+            let habitat_bubbles = self
+                .soft_bodies
+                .iter()
+                .filter(|(_, soft_body)| {
+                    soft_body
+                        .actors
+                        .iter()
+                        .any(|actor| matches!(actor, Actor::HabitatBubble { .. }))
+                })
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>();
 
-                i += 1;
-            }
+            self.destroy_soft_bodies(&habitat_bubbles);
 
             let key = self
                 .soft_bodies
-                .insert(crate::habitat_bubble(vec2(-5.0, 0.0)));
+                .insert(prefabs::habitat_bubble(vec2(-5.0, 0.0)).build());
 
             self.keys.push(key);
         }
     }
 
+    /// Moves the selected soft body with the arrow keys, or rotates it around its centroid while
+    /// Shift is held. Velocities are cleared so any connected bodies aren't flung by their
+    /// constraints.
+    pub fn update_nudging(&mut self) {
+        if self.input_state.keybind_focus.is_some() || self.input_state.typing {
+            return;
+        }
+
+        let Some(soft_body) = self
+            .input_state
+            .selected_soft_body
+            .and_then(|key| self.soft_bodies.get_mut(key))
+        else {
+            return;
+        };
+
+        let mut direction = Vec2::ZERO;
+
+        direction.x += input::is_key_pressed(KeyCode::Right) as u8 as f32;
+        direction.x -= input::is_key_pressed(KeyCode::Left) as u8 as f32;
+        direction.y += input::is_key_pressed(KeyCode::Up) as u8 as f32;
+        direction.y -= input::is_key_pressed(KeyCode::Down) as u8 as f32;
+
+        if direction == Vec2::ZERO {
+            return;
+        }
+
+        if input::is_key_down(KeyCode::LeftShift) || input::is_key_down(KeyCode::RightShift) {
+            if direction.x != 0.0 {
+                soft_body.rotate_around(soft_body.centroid(), -direction.x * Self::NUDGE_ANGLE);
+            }
+        } else {
+            soft_body.translate(direction * Self::NUDGE_DISTANCE);
+        }
+    }
+
+    /// Copies the ship of the selected soft body with Control + C, and pastes a ship at the mouse
+    /// with Control + V
+    pub fn update_clipboard(&mut self) {
+        if self.input_state.keybind_focus.is_some()
+            || self.input_state.controls_focus.is_some()
+            || self.input_state.typing
+            || !(input::is_key_down(KeyCode::LeftControl)
+                || input::is_key_down(KeyCode::RightControl))
+        {
+            return;
+        }
+
+        let message = if input::is_key_pressed(KeyCode::C)
+            && let Some(key) = self.input_state.selected_soft_body
+        {
+            let count = ship_clipboard::copy(self, key);
+
+            format!("Copied {count} soft bodies")
+        } else if input::is_key_pressed(KeyCode::V) {
+            match ship_clipboard::paste(self, self.input_state.mouse.position) {
+                Some(keys) => {
+                    let message = format!("Pasted {} soft bodies", keys.len());
+
+                    self.input_state.selection = keys;
+
+                    message
+                }
+                None => "The clipboard doesn't contain a ship".to_owned(),
+            }
+        } else {
+            return;
+        };
+
+        self.input_state.clipboard_message = Some((message, time::get_time()));
+    }
+
     pub fn update_gui(&mut self) {
+        let mut confirmed = None;
+
         egui_macroquad::ui(|egui| {
             self.update_keybind_editor(egui);
+            self.show_fuel(egui);
+            self.show_clipboard_message(egui);
+            self.show_objective(egui);
+            self.show_body_budget_warning(egui);
+
+            let phase_timings = mem::take(&mut self.phase_timings);
+
+            if self.show_phase_timings {
+                Self::show_phase_timings(phase_timings, egui);
+            }
+            confirmed = self.update_confirmation(egui);
 
             self.input_state.ui_hovered = egui.is_pointer_over_area();
+            self.input_state.typing = egui.wants_keyboard_input();
         });
+
+        match confirmed {
+            Some(SceneCommand::Clear) => self.clear(),
+            Some(SceneCommand::Reset) => self.reset_to_default(),
+            None => (),
+        }
+    }
+
+    pub fn show_fuel(&self, egui: &Context) {
+        let Some((fuel, fuel_capacity)) = self.followed_ship_fuel() else {
+            return;
+        };
+
+        egui::Area::new(egui::Id::new("fuel"))
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+            .show(egui, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(format!("Fuel: {fuel:.0} / {fuel_capacity:.0}"));
+
+                    if self.input_state.autopilot {
+                        ui.label("Autopilot holding position");
+                    }
+
+                    ui.add(
+                        egui::ProgressBar::new(fuel / fuel_capacity.max(f32::EPSILON))
+                            .desired_width(150.0),
+                    );
+                });
+            });
+    }
+
+    /// Shows how long each phase of the ticks since the last frame took
+    pub fn show_phase_timings(timings: PhaseTimings, egui: &Context) {
+        let total = timings.total();
+
+        egui::Area::new(egui::Id::new("phase_timings"))
+            .anchor(egui::Align2::LEFT_BOTTOM, [10.0, -10.0])
+            .show(egui, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(format!(
+                        "{} ticks: {:.2} ms",
+                        timings.ticks,
+                        total.as_secs_f64() * 1000.0,
+                    ));
+
+                    for (name, duration) in timings.iter() {
+                        ui.add(
+                            egui::ProgressBar::new(
+                                (duration.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON))
+                                    as f32,
+                            )
+                            .desired_width(200.0)
+                            .text(format!("{name}: {:.2} ms", duration.as_secs_f64() * 1000.0)),
+                        );
+                    }
+                });
+            });
+    }
+
+    pub fn show_clipboard_message(&mut self, egui: &Context) {
+        let Some((message, copied_time)) = &self.input_state.clipboard_message else {
+            return;
+        };
+
+        if time::get_time() - copied_time > Self::CLIPBOARD_MESSAGE_DURATION {
+            self.input_state.clipboard_message = None;
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("clipboard"))
+            .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -10.0])
+            .show(egui, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(message);
+                });
+            });
+    }
+
+    pub fn show_body_budget_warning(&self, egui: &Context) {
+        if !self.over_budget {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("body_budget"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+            .show(egui, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!(
+                            "There are {} bodies, more than the limit of {}. Remove some to keep \
+                            the simulation fast.",
+                            self.soft_bodies.len(),
+                            self.max_bodies,
+                        ),
+                    );
+                });
+            });
+    }
+
+    pub fn show_objective(&self, egui: &Context) {
+        let Some(win_condition) = self.win_condition else {
+            return;
+        };
+
+        egui::Area::new(egui::Id::new("objective"))
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 10.0])
+            .show(egui, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    if self.objective_complete {
+                        ui.label("Objective complete.");
+                    } else {
+                        ui.label(win_condition.description());
+                    }
+                });
+            });
+    }
+
+    /// Shows a window asking to confirm [`InputState::confirming`], and returns it once confirmed
+    pub fn update_confirmation(&mut self, egui: &Context) -> Option<SceneCommand> {
+        let command = self.input_state.confirming?;
+
+        let mut confirmed = false;
+        let mut cancelled = input::is_key_pressed(KeyCode::Escape);
+
+        egui::Window::new("Confirm")
+            .resizable(false)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(egui, |ui| {
+                ui.label(command.description());
+
+                ui.horizontal(|ui| {
+                    confirmed |= ui.button("Yes").clicked();
+                    cancelled |= ui.button("No").clicked();
+                });
+            });
+
+        if confirmed || cancelled {
+            self.input_state.confirming = None;
+        }
+
+        confirmed.then_some(command)
     }
 
     pub fn update_keybind_editor(&mut self, egui: &Context) {
         egui.set_zoom_factor(window::screen_dpi_scale() / 2.0 * window::screen_width() / 750.0);
 
+        // Worked out before the window borrows the input state
+        let ship_stats = self
+            .input_state
+            .selected_soft_body
+            .filter(|&key| self.soft_bodies.contains_key(key))
+            .map(|key| self.ship_stats(key));
+
         let window = egui::Window::new("Info")
             .resizable(false)
             .movable(false)
@@ -586,6 +2594,7 @@ impl Simulation {
             .open(&mut self.input_state.editing);
 
         let mut close_window = false;
+        let mut copy_keybinds = false;
 
         window.show(egui, |ui| {
             let Some(soft_body_key) = self.input_state.selected_soft_body else {
@@ -597,12 +2606,100 @@ impl Simulation {
                         press Shift + Control + R to create a new one.");
                     ui.label("Click and drag on a white line to connect it to another or move it around. After \
                         being connected, click on it again to disconnect.");
-                    ui.label("Right click on an interactible to view and edit its keybinds. It can be used when \
-                        connected to your habitat bubble.");
+                    ui.label("Drag a white line onto one which is already connected to join all of them at one \
+                        junction. Clicking any line at a junction disconnects just that one, unless it is the \
+                        one the others were joined to.");
+                    ui.label("Click and drag anywhere else inside of something to move it around by its nearest \
+                        edge.");
+                    ui.label("Right click on anything to inspect its physics, or on an interactible to view and \
+                        edit its keybinds. It can be used when connected to your habitat bubble.");
                     ui.label("You can drag the edge of your habitat bubble to move. Scroll to zoom the camera.");
-                    if ui.add(Label::new("Press F1 to toggle this menu.").sense(Sense::click())).clicked() {
+                    ui.label(format!("While dragging a white line onto another, press {:?} to rotate it into \
+                        line.", self.controls.align));
+                    ui.label(format!("Press {:?} to dim everything except the interactible you are editing.",
+                        self.controls.solo));
+                    ui.label(format!("Press {:?} to let the autopilot stop your ship from moving and spinning \
+                        with its rocket motors and reaction wheels.", self.controls.autopilot));
+                    ui.label("Use the arrow keys to nudge the interactible you are editing, or hold Shift to \
+                        rotate it.");
+                    ui.label(format!("Hold {:?} while dragging a white line to move everything connected to it.",
+                        self.controls.drag_ship));
+                    ui.label(format!("Press {:?} to show every white line and how many points long it is.",
+                        self.controls.attatchment_points));
+                    ui.label(format!("Press {:?} to trace the path the interactible you are editing has taken.",
+                        self.controls.trace));
+                    ui.label(format!("Press {:?} to freeze the interactible you are editing in place, so nothing \
+                        can move it.", self.controls.freeze));
+                    ui.label("While editing keybinds, press Tab to move between keys and Enter to change one.");
+                    ui.label("Drag with the right mouse button to select several interactibles, then copy the \
+                        keybinds of the one you are editing to them.");
+                    ui.label("Press Control + C to copy the ship of the interactible you are editing, and \
+                        Control + V to paste a ship at the mouse, even into another window.");
+                    if ui.add(Label::new(format!("Press {:?} to toggle this menu.", self.controls.menu))
+                        .sense(Sense::click())).clicked() {
                         close_window = true;
                     };
+
+                    ui.add_space(5.0);
+
+                    ui.collapsing("Spawn", |ui| {
+                        for (i, (name, _)) in prefabs::PALETTE.into_iter().enumerate() {
+                            let selected = self.input_state.spawning == Some(i);
+
+                            if ui.selectable_label(selected, name).clicked() {
+                                self.input_state.spawning = (!selected).then_some(i);
+                            }
+                        }
+
+                        if let Some(i) = self.input_state.spawning {
+                            ui.label(format!("Click to place a {}.", prefabs::PALETTE[i].0.to_lowercase()));
+
+                            if self.input_state.spawn_blocked {
+                                ui.colored_label(egui::Color32::RED, "It can't overlap another body.");
+                            }
+                        }
+                    });
+
+                    ui.collapsing("Controls", |ui| {
+                        Self::show_controls_editor(
+                            &mut self.controls,
+                            &mut self.input_state.controls_focus,
+                            ui,
+                        );
+                    });
+
+                    ui.collapsing("Grabbing", |ui| {
+                        ui.label("Dragging");
+                        Self::show_joining_spring_editor(&mut self.grab_spring, Self::DEFAULT_GRAB_SPRING, ui);
+
+                        ui.add_space(5.0);
+
+                        ui.label("Connecting");
+                        Self::show_joining_spring_editor(&mut self.pull_spring, Self::DEFAULT_PULL_SPRING, ui);
+
+                        ui.add_space(5.0);
+
+                        ui.label("Spin damping")
+                            .on_hover_text("Slows the spinning of whatever is being dragged");
+                        ui.add(Slider::new(&mut self.grab_angular_damping, 0.0..=20.0));
+
+                        ui.add_space(5.0);
+
+                        ui.horizontal(|ui| {
+                            let mut filtering = self.input_state.seam_length_filter.is_some();
+
+                            ui.checkbox(&mut filtering, "Only seams of length")
+                                .on_hover_text("Only seams of this length can be selected");
+
+                            let mut length = self.input_state.seam_length_filter.unwrap_or(1);
+                            ui.add_enabled(
+                                filtering,
+                                egui::DragValue::new(&mut length).range(1..=Self::MAX_SEAM_LENGTH_FILTER),
+                            );
+
+                            self.input_state.seam_length_filter = filtering.then_some(length);
+                        });
+                    });
                 }
 
                 return;
@@ -617,10 +2714,18 @@ impl Simulation {
                 self.input_state.keybind_focus = None;
             }
 
+            let mut keybind_id = 0;
+
             let mut show_keybind = |name: &str, keybind: &mut Keybind, ui: &mut Ui| {
+                let id = keybind_id;
+                keybind_id += 1;
+
+                // A key that starts capturing by pressing Enter shouldn't also be captured
+                let previous_focus = self.input_state.keybind_focus;
+
                 let mut show_key = |focus: KeybindFocus, key: Option<&KeyCode>, ui: &mut Ui| {
                     ui.horizontal(|ui| {
-                        let focused = self.input_state.keybind_focus == Some(focus);
+                        let focused = self.input_state.keybind_focus == Some((id, focus));
 
                         let size = if key.is_some() {
                             egui::vec2(150.0, 0.0)
@@ -639,12 +2744,21 @@ impl Simulation {
                         }
                         .min_size(size);
 
-                        if ui
+                        let response = ui
                             .add(button)
-                            .on_hover_text("Backspace or Delete to remove")
-                            .clicked()
-                        {
-                            self.input_state.keybind_focus = Some(focus);
+                            .on_hover_text("Backspace or Delete to remove");
+
+                        if response.has_focus() {
+                            ui.painter().rect_stroke(
+                                response.rect.expand(2.0),
+                                2.0,
+                                ui.visuals().selection.stroke,
+                                StrokeKind::Outside,
+                            );
+                        }
+
+                        if response.clicked() {
+                            self.input_state.keybind_focus = Some((id, focus));
                         }
                     });
                 };
@@ -667,10 +2781,12 @@ impl Simulation {
                 ui.add_space(2.5);
                 show_key(KeybindFocus::NewDisable, None, ui);
 
-                if let (Some(keybind_focus), Some(key_code)) = (
+                if let (Some((focus_id, keybind_focus)), Some(key_code)) = (
                     self.input_state.keybind_focus,
                     input::get_last_key_pressed(),
-                ) {
+                ) && focus_id == id
+                    && self.input_state.keybind_focus == previous_focus
+                {
                     if key_code == KeyCode::Escape
                         || Some(key_code) == keybind.get(keybind_focus)
                         || (KeyCode::F1 as u16..=KeyCode::F25 as u16).contains(&(key_code as u16))
@@ -700,32 +2816,140 @@ impl Simulation {
                 }
             };
 
+            let mut remove_actor = None;
+            let num_points = soft_body.shape.len();
+
             for (i, actor) in soft_body.actors.iter_mut().enumerate() {
                 if i != 0 {
                     ui.add_space(5.0);
                 }
 
-                match actor {
-                    Actor::RocketMotor {
-                        force,
-                        enable,
-                        max_particle_time,
-                        ..
-                    } => {
-                        show_keybind("Enable Thrust", enable, ui);
+                match actor {
+                    Actor::RocketMotor {
+                        line,
+                        force,
+                        enable,
+                        max_particle_time,
+                        gimbal,
+                        exhaust_start,
+                        exhaust_end,
+                        ..
+                    } => {
+                        show_keybind("Enable Thrust", enable, ui);
+                        ui.add_space(5.0);
+
+                        ui.label("Edge");
+                        ui.add(Slider::new(line, 0..=num_points - 1));
+
+                        ui.add_space(5.0);
+
+                        ui.label("Force");
+                        let length = force.length();
+                        let mut new_length = length;
+                        ui.add(Slider::new(&mut new_length, 25.0..=200.0));
+
+                        if length != new_length {
+                            *force = force.normalize_or_zero() * new_length;
+                            *max_particle_time = 0.5 / new_length;
+                        }
+
+                        ui.add_space(5.0);
+
+                        ui.horizontal(|ui| {
+                            ui.label("Exhaust");
+                            Self::show_color_picker(exhaust_start, ui);
+                            Self::show_color_picker(exhaust_end, ui);
+                        });
+
+                        ui.add_space(5.0);
+
+                        let mut has_gimbal = gimbal.is_some();
+                        ui.checkbox(&mut has_gimbal, "Gimbal");
+
+                        if has_gimbal != gimbal.is_some() {
+                            *gimbal = has_gimbal.then(Gimbal::default);
+                        }
+
+                        if let Some(gimbal) = gimbal {
+                            ui.label("Gimbal range");
+                            ui.add(
+                                Slider::new(&mut gimbal.max_angle, 0.0..=FRAC_PI_4)
+                                    .custom_formatter(|angle, _| {
+                                        format!("{:.0}°", angle.to_degrees())
+                                    })
+                                    .custom_parser(|text| {
+                                        text.trim_end_matches('°')
+                                            .parse::<f64>()
+                                            .ok()
+                                            .map(f64::to_radians)
+                                    }),
+                            );
+                            ui.label(format!("Current angle: {:.1}°", gimbal.angle.to_degrees()));
+
+                            ui.add_space(5.0);
+                            show_keybind("Steer Left", &mut gimbal.steer_left, ui);
+                            ui.add_space(5.0);
+                            show_keybind("Steer Right", &mut gimbal.steer_right, ui);
+                        }
+                    }
+                    Actor::HabitatBubble { .. } => (),
+                    Actor::ReactionWheel {
+                        torque,
+                        enable_cw,
+                        enable_ccw,
+                    } => {
+                        show_keybind("Spin Clockwise", enable_cw, ui);
+                        ui.add_space(5.0);
+                        show_keybind("Spin Counterclockwise", enable_ccw, ui);
+                        ui.add_space(5.0);
+
+                        ui.label("Torque");
+                        ui.add(Slider::new(torque, 5.0..=100.0));
+                    }
+                    Actor::OrientationSpring {
+                        line,
+                        target_direction,
+                        stiffness,
+                        damping,
+                    } => {
+                        ui.label("Edge");
+                        ui.add(Slider::new(line, 0..=num_points - 1));
+
+                        ui.add_space(5.0);
+
+                        ui.label("Direction");
+                        let mut angle = target_direction.to_angle();
+                        ui.add(Slider::new(&mut angle, -PI..=PI).custom_formatter(|angle, _| {
+                            format!("{:.0}°", angle.to_degrees())
+                        }));
+                        *target_direction = Vec2::from_angle(angle);
+
+                        ui.add_space(5.0);
+
+                        ui.label("Stiffness");
+                        ui.add(Slider::new(stiffness, 1.0..=200.0).logarithmic(true));
+
+                        ui.label("Damping");
+                        ui.add(Slider::new(damping, 0.0..=100.0));
+                    }
+                    Actor::Label { offset, text, size } => {
+                        ui.heading("Label");
+                        ui.text_edit_singleline(text);
+
                         ui.add_space(5.0);
 
-                        ui.label("Force");
-                        let length = force.length();
-                        let mut new_length = length;
-                        ui.add(Slider::new(&mut new_length, 25.0..=200.0));
+                        ui.label("Size");
+                        ui.add(Slider::new(size, 0.1..=5.0).logarithmic(true));
+
+                        ui.add_space(5.0);
+
+                        ui.horizontal(|ui| {
+                            ui.label("Offset");
+                            ui.add(egui::DragValue::new(&mut offset.x).speed(0.05).prefix("x: "));
+                            ui.add(egui::DragValue::new(&mut offset.y).speed(0.05).prefix("y: "));
+                        });
 
-                        if length != new_length {
-                            *force = force.normalize_or_zero() * new_length;
-                            *max_particle_time = 0.5 / new_length;
-                        }
                     }
-                    Actor::HabitatBubble { .. } => (),
                     Actor::Piston { lengths, enable } => {
                         show_keybind("Extend", enable, ui);
 
@@ -764,23 +2988,329 @@ impl Simulation {
                         }
                     }
                 }
+
+                if !matches!(actor, Actor::HabitatBubble { .. }) {
+                    ui.add_space(5.0);
+
+                    if ui.button("Remove").clicked() {
+                        remove_actor = Some(i);
+                    }
+                }
+            }
+
+            if let Some(i) = remove_actor {
+                soft_body.remove_actor(i);
+            }
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                let mut new_actor = None;
+
+                if ui.button("Add rocket motor").clicked() {
+                    new_actor = Some(Actor::new_rocket_motor(0));
+                }
+
+                if ui.button("Add reaction wheel").clicked() {
+                    new_actor = Some(Actor::new_reaction_wheel());
+                }
+
+                if ui.button("Add stabilizer").clicked() {
+                    new_actor = Some(Actor::new_orientation_spring(0, Vec2::X));
+                }
+
+                if ui.button("Add label").clicked() {
+                    new_actor = Some(Actor::new_label());
+                }
+
+                if let Some(actor) = new_actor {
+                    soft_body.add_actor(actor).unwrap();
+                }
+            });
+
+            self.input_state
+                .selection
+                .retain(|&key| key != soft_body_key);
+
+            if soft_body.uses_keybinds() && !self.input_state.selection.is_empty() {
+                ui.add_space(5.0);
+
+                copy_keybinds = ui
+                    .button(format!(
+                        "Copy keybinds to {} selected",
+                        self.input_state.selection.len()
+                    ))
+                    .clicked();
+            }
+
+            ui.add_space(5.0);
+
+            ui.collapsing("Physics", |ui| Self::show_inspector(soft_body, ui));
+
+            if let Some(ship_stats) = ship_stats {
+                ui.collapsing("Ship", |ui| Self::show_ship_stats(ship_stats, ui));
             }
         });
 
+        if copy_keybinds && let Some(soft_body_key) = self.input_state.selected_soft_body {
+            let selection = self.input_state.selection.clone();
+
+            self.copy_keybinds(soft_body_key, &selection);
+        }
+
         if close_window {
             self.input_state.editing = false;
         }
     }
 
+    /// Gives each actor on the `to` soft bodies the keybinds of the first actor of the same kind
+    /// on the `from` soft body
+    pub fn copy_keybinds(&mut self, from: SoftBodyKey, to: &[SoftBodyKey]) {
+        let Some(source) = self.soft_bodies.get(from) else {
+            return;
+        };
+
+        let keybinds = source
+            .actors
+            .iter()
+            .filter(|actor| actor.uses_keybinds())
+            .map(|actor| {
+                (
+                    mem::discriminant(actor),
+                    actor.keybinds().into_iter().cloned().collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for &key in to {
+            let Some(soft_body) = self.soft_bodies.get_mut(key) else {
+                continue;
+            };
+
+            for actor in &mut soft_body.actors {
+                let kind = mem::discriminant(actor);
+
+                if let Some((_, source_keybinds)) = keybinds
+                    .iter()
+                    .find(|(source_kind, _)| *source_kind == kind)
+                {
+                    for (keybind, source_keybind) in
+                        actor.keybinds_mut().into_iter().zip(source_keybinds)
+                    {
+                        *keybind = source_keybind.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn show_ship_stats(ship_stats: ShipStats, ui: &mut Ui) {
+        ui.label(format!("Thrust: {:.1}", ship_stats.thrust));
+        ui.label(format!("Mass: {:.2}", ship_stats.mass));
+        ui.label(format!(
+            "Maximum acceleration: {:.2}",
+            ship_stats.max_acceleration
+        ));
+
+        if let Some(delta_v) = ship_stats.delta_v {
+            ui.label(format!("Delta-v: {delta_v:.1}"));
+        } else {
+            ui.label("Delta-v: no fuel tank");
+        }
+    }
+
+    /// Shows live readouts of a soft body's physical state, and edits its gas force, mass,
+    /// friction, and springs as a whole
+    pub fn show_inspector(soft_body: &mut SoftBody, ui: &mut Ui) {
+        let centroid = soft_body.centroid();
+
+        ui.label(format!("Points: {}", soft_body.shape.len()));
+        ui.label(format!("Area: {:.3}", soft_body.area()));
+        ui.label(format!("Pressure: {:.3}", soft_body.pressure));
+        ui.label(format!("Centroid: ({:.2}, {:.2})", centroid.x, centroid.y));
+
+        ui.add_space(5.0);
+
+        ui.label("Gas force");
+        // Sliders in the inspector never clamp, so that just showing them doesn't change values
+        // outside of their range
+        ui.add(Slider::new(&mut soft_body.gas_force, 0.0..=100.0).clamping(SliderClamping::Never));
+
+        ui.label("Mass");
+        let mass = soft_body
+            .shape
+            .iter()
+            .map(|(point, _)| point.mass)
+            .sum::<f32>();
+        let mut new_mass = mass;
+        let response = ui.add(
+            Slider::new(&mut new_mass, 0.1..=100.0)
+                .logarithmic(true)
+                .clamping(SliderClamping::Never),
+        );
+
+        if response.changed() && mass != new_mass {
+            // Scales every point by the same amount so the mass distribution is kept
+            for (point, _) in &mut soft_body.shape {
+                point.mass = (point.mass * new_mass / mass).max(Point::MINIMUM_MASS);
+            }
+        }
+
+        ui.label("Friction");
+        let friction = soft_body
+            .shape
+            .first()
+            .map_or(0.0, |(_, line)| line.friction);
+        let mut new_friction = friction;
+        let response =
+            ui.add(Slider::new(&mut new_friction, 0.0..=2.0).clamping(SliderClamping::Never));
+
+        if response.changed() && friction != new_friction {
+            for (_, line) in &mut soft_body.shape {
+                line.friction = new_friction;
+            }
+        }
+
+        ui.add_space(5.0);
+
+        ui.label("Spring stiffness");
+        Self::show_scale_slider(
+            soft_body
+                .linear_springs_mut()
+                .map(|spring| &mut spring.force_constant)
+                .collect(),
+            1.0..=10000.0,
+            ui,
+        );
+
+        ui.label("Spring damping");
+        Self::show_scale_slider(
+            soft_body
+                .linear_springs_mut()
+                .map(|spring| &mut spring.damping)
+                .collect(),
+            0.1..=1000.0,
+            ui,
+        );
+
+        ui.label("Angular stiffness");
+        Self::show_scale_slider(
+            soft_body
+                .angular_springs_mut()
+                .map(|spring| &mut spring.force_constant)
+                .collect(),
+            1.0..=10000.0,
+            ui,
+        );
+
+        ui.label("Angular damping");
+        Self::show_scale_slider(
+            soft_body
+                .angular_springs_mut()
+                .map(|spring| &mut spring.damping)
+                .collect(),
+            0.1..=1000.0,
+            ui,
+        );
+    }
+
+    /// Shows a button which opens a color picker for `color`, including its alpha
+    fn show_color_picker(color: &mut Color, ui: &mut Ui) {
+        let mut rgba = [color.r, color.g, color.b, color.a];
+
+        if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+            *color = Color::from(rgba);
+        }
+    }
+
+    /// Shows the average of `values`, and scales every value by the same amount when it is
+    /// changed, so their differences are kept. An average outside of `range` is left alone until
+    /// the slider is dragged.
+    fn show_scale_slider(values: Vec<&mut f32>, range: RangeInclusive<f32>, ui: &mut Ui) {
+        let average = values.iter().map(|value| **value).sum::<f32>() / values.len().max(1) as f32;
+        let mut new_average = average;
+        let response = ui.add_enabled(
+            average > 0.0,
+            Slider::new(&mut new_average, range)
+                .logarithmic(true)
+                .clamping(SliderClamping::Never),
+        );
+
+        if response.changed() && average > 0.0 && average != new_average {
+            for value in values {
+                *value *= new_average / average;
+            }
+        }
+    }
+
+    /// Edits the strength and damping of `spring`. How far it can stretch before its force stops
+    /// growing is left alone, which together with the limited range of the strength keeps it from
+    /// launching light soft bodies.
+    pub fn show_joining_spring_editor(
+        spring: &mut JoiningSpring,
+        default: JoiningSpring,
+        ui: &mut Ui,
+    ) {
+        ui.label("Strength");
+        ui.add(Slider::new(&mut spring.force_constant, 1.0..=100.0).logarithmic(true));
+
+        ui.label("Damping");
+        ui.add(Slider::new(&mut spring.normal_damping, 0.0..=25.0));
+
+        ui.label("Sideways damping");
+        ui.add(Slider::new(&mut spring.perpendicular_damping, 0.0..=25.0));
+
+        if ui.button("Reset").clicked() {
+            *spring = default;
+        }
+    }
+
+    pub fn show_controls_editor(
+        controls: &mut Controls,
+        controls_focus: &mut Option<usize>,
+        ui: &mut Ui,
+    ) {
+        if input::is_mouse_button_pressed(MouseButton::Left) {
+            *controls_focus = None;
+        }
+
+        for (i, (name, key_code)) in controls.iter_mut().into_iter().enumerate() {
+            ui.horizontal(|ui| {
+                let focused = *controls_focus == Some(i);
+
+                let button = if focused {
+                    Button::new("press a key")
+                } else {
+                    Button::new(format!("{key_code:?}"))
+                }
+                .min_size(egui::vec2(100.0, 0.0));
+
+                if ui.add(button).clicked() {
+                    *controls_focus = Some(i);
+                }
+
+                ui.label(name);
+
+                if focused && let Some(new_key_code) = input::get_last_key_pressed() {
+                    if new_key_code != KeyCode::Escape {
+                        *key_code = new_key_code;
+                    }
+
+                    *controls_focus = None;
+                }
+            });
+        }
+    }
+
     pub fn update_grabbing(&mut self, dt: f32) {
         if let Some((handle, progress)) = self.input_state.selected_attatchment_point {
             if !self.soft_bodies.contains_key(handle.soft_body) {
                 self.input_state.selected_attatchment_point = None;
             } else if self.input_state.grabbing {
                 if self.input_state.clicking
-                    && self.soft_bodies[handle.soft_body].attatchment_points[handle.index]
-                        .connection
-                        .is_some()
+                    && !self.soft_bodies[handle.soft_body].attatchment_points[handle.index]
+                        .connections
+                        .is_empty()
                 {
                     self.disconnect_attatchment_point(handle).unwrap();
                     self.input_state.selected_attatchment_point = None;
@@ -790,24 +3320,52 @@ impl Simulation {
 
                 if let Some(target) = self.input_state.target_attatchment_point {
                     self.push_together([handle, target], dt);
+                } else if input::is_key_down(self.controls.drag_ship)
+                    && self.soft_bodies[handle.soft_body]
+                        .connection_state
+                        .is_connected()
+                {
+                    self.push_ship_towards_mouse(handle, progress, dt);
                 } else {
                     self.push_towards_mouse(handle, progress, dt);
                 }
             }
         }
+
+        if let Some((key, line, interpolation)) = self.input_state.grabbed_line {
+            let Some(soft_body) = self.soft_bodies.get(key) else {
+                self.input_state.grabbed_line = None;
+                return;
+            };
+
+            if line >= soft_body.shape.len() {
+                self.input_state.grabbed_line = None;
+            } else if input::is_key_down(self.controls.drag_ship)
+                && soft_body.connection_state.is_connected()
+            {
+                self.push_ship_from_line_towards_mouse(key, line, interpolation, dt);
+            } else {
+                self.push_line_towards_mouse(key, line, interpolation, dt);
+            }
+        }
     }
 
+    /// Does nothing if both handles point to the same soft body or either soft body doesn't exist
     pub fn push_together(&mut self, [handle_a, handle_b]: [AttatchmentPointHandle; 2], dt: f32) {
-        let [soft_body_a, soft_body_b] = self
+        let pull_spring = self.pull_spring;
+
+        let Some([soft_body_a, soft_body_b]) = self
             .soft_bodies
             .get_disjoint_mut([handle_a.soft_body, handle_b.soft_body])
-            .unwrap();
+        else {
+            return;
+        };
 
         let length_a = soft_body_a.shape.len();
         let length_b = soft_body_b.shape.len();
 
-        let attatchment_point_a = soft_body_a.attatchment_points[handle_a.index];
-        let attatchment_point_b = soft_body_b.attatchment_points[handle_b.index];
+        let attatchment_point_a = soft_body_a.attatchment_points[handle_a.index].clone();
+        let attatchment_point_b = soft_body_b.attatchment_points[handle_b.index].clone();
 
         let mut mass_moment_a = Vec2::ZERO;
         let mut mass_moment_b = Vec2::ZERO;
@@ -836,7 +3394,7 @@ impl Simulation {
                 total_mass_a += point_a.mass;
                 total_mass_b += point_b.mass;
 
-                let (_, _, impulse, _) = Self::PULL_SPRING.get_force(point_a, point_b);
+                let (_, _, impulse, _) = pull_spring.get_force(point_a, point_b);
 
                 point_a.impulse += impulse / 2.0 * dt * point_a.mass;
             }
@@ -872,13 +3430,13 @@ impl Simulation {
                 let (point_a, _) = &mut soft_body_a.shape[point_a];
                 let (point_b, _) = &mut soft_body_b.shape[point_b];
 
-                let mut moved_point_b = Point {
+                let moved_point_b = Point {
                     position: point_b.position + position_offset,
                     velocity: point_b.velocity + velocity_offset,
                     ..*point_b
                 };
 
-                let (_, _, impulse, _) = Self::ALIGN_SPRING.get_force(point_a, &mut moved_point_b);
+                let (_, _, impulse, _) = Self::ALIGN_SPRING.get_force(point_a, &moved_point_b);
 
                 point_a.impulse += impulse / 2.0 * dt * point_a.mass;
                 point_b.impulse -= impulse / 2.0 * dt * point_b.mass;
@@ -905,7 +3463,7 @@ impl Simulation {
         let soft_body = &mut self.soft_bodies[handle.soft_body];
         let length = soft_body.shape.len();
 
-        let attatchment_point = soft_body.attatchment_points[handle.index];
+        let attatchment_point = soft_body.attatchment_points[handle.index].clone();
 
         let (point_a, _, point_b) = soft_body
             .get_line_mut((attatchment_point.start_point + line_offset) % length)
@@ -923,59 +3481,258 @@ impl Simulation {
         let mut mouse = Point {
             velocity: composite_point.velocity.lerp(
                 self.input_state.mouse.velocity,
-                1.0 / (self.input_state.mouse.position)
+                1.0 / self
+                    .input_state
+                    .mouse
+                    .position
                     .distance_squared(composite_point.position)
                     .max(1.0),
             ),
             ..self.input_state.mouse
         };
 
-        Self::GRAB_SPRING.apply_force(&mut mouse, &mut composite_point, dt);
+        self.grab_spring
+            .apply_force(&mut mouse, &mut composite_point, dt);
 
         let impulse = composite_point.impulse;
 
-        let mut i = attatchment_point.start_point;
-
-        for _ in 0..attatchment_point.length {
+        for i in soft_body.attatchment_point_indecies(handle.index) {
             let (point, _) = &mut soft_body.shape[i];
 
             point.impulse += impulse * point.mass;
+        }
+
+        soft_body.damp_angular_velocity(self.grab_angular_damping, dt);
+    }
+
+    /// Like [`Self::push_towards_mouse`], but pulls on the point `interpolation` of the way along
+    /// one edge of the soft body, and only accelerates that edge's two points
+    pub fn push_line_towards_mouse(
+        &mut self,
+        key: SoftBodyKey,
+        line: usize,
+        interpolation: f32,
+        dt: f32,
+    ) {
+        let soft_body = &mut self.soft_bodies[key];
+
+        let (point_a, _, point_b) = soft_body.get_line_mut(line).unwrap();
+
+        let interpolation_scale = utils::interpolation_scale(interpolation);
+
+        let mut composite_point = Point {
+            position: point_a.position.lerp(point_b.position, interpolation),
+            velocity: point_a.velocity.lerp(point_b.velocity, interpolation),
+            mass: utils::lerp(point_a.mass, point_b.mass, interpolation) * interpolation_scale,
+            ..Default::default()
+        };
+
+        let mut mouse = Point {
+            velocity: composite_point.velocity.lerp(
+                self.input_state.mouse.velocity,
+                1.0 / self
+                    .input_state
+                    .mouse
+                    .position
+                    .distance_squared(composite_point.position)
+                    .max(1.0),
+            ),
+            ..self.input_state.mouse
+        };
+
+        self.grab_spring
+            .apply_force(&mut mouse, &mut composite_point, dt);
+
+        let impulse = composite_point.impulse;
+
+        point_a.impulse += impulse * point_a.mass;
+        point_b.impulse += impulse * point_b.mass;
+
+        soft_body.damp_angular_velocity(self.grab_angular_damping, dt);
+    }
+
+    /// Like [`Self::push_towards_mouse`], but accelerates every point of the soft body's
+    /// [connected component](Self::connected_component) equally, so that the whole ship moves
+    /// without turning.
+    pub fn push_ship_towards_mouse(
+        &mut self,
+        handle: AttatchmentPointHandle,
+        progress: f32,
+        dt: f32,
+    ) {
+        let soft_body = &self.soft_bodies[handle.soft_body];
+        let attatchment_point = &soft_body.attatchment_points[handle.index];
+
+        let line =
+            (attatchment_point.start_point + progress.floor() as usize) % soft_body.shape.len();
+
+        self.push_ship_from_line_towards_mouse(
+            handle.soft_body,
+            line,
+            progress.rem_euclid(1.0),
+            dt,
+        );
+    }
+
+    /// Like [`Self::push_ship_towards_mouse`], but pulls on the point `interpolation` of the way
+    /// along one edge of the soft body
+    pub fn push_ship_from_line_towards_mouse(
+        &mut self,
+        key: SoftBodyKey,
+        line: usize,
+        interpolation: f32,
+        dt: f32,
+    ) {
+        let component = self.connected_component(key);
+
+        let (point_a, _, point_b) = self.soft_bodies[key].get_line(line).unwrap();
+
+        let (mass, momentum) =
+            component
+                .iter()
+                .fold((0.0, Vec2::ZERO), |(mass, momentum), &key| {
+                    let soft_body = &self.soft_bodies[key];
+
+                    (
+                        mass + soft_body.total_mass(),
+                        momentum + soft_body.velocity() * soft_body.total_mass(),
+                    )
+                });
+
+        let mut composite_point = Point {
+            position: point_a.position.lerp(point_b.position, interpolation),
+            velocity: momentum / mass,
+            mass,
+            ..Default::default()
+        };
+
+        let mut mouse = Point {
+            velocity: composite_point.velocity.lerp(
+                self.input_state.mouse.velocity,
+                1.0 / self
+                    .input_state
+                    .mouse
+                    .position
+                    .distance_squared(composite_point.position)
+                    .max(1.0),
+            ),
+            ..self.input_state.mouse
+        };
+
+        self.grab_spring
+            .apply_force(&mut mouse, &mut composite_point, dt);
+
+        let impulse = composite_point.impulse;
+
+        for key in component {
+            for (point, _) in &mut self.soft_bodies[key].shape {
+                point.impulse += impulse * point.mass;
+            }
+        }
+    }
+
+    /// Records the centroid of the selected soft body into [`Self::trace`] every
+    /// [`Self::TRACE_INTERVAL`] ticks while [`Self::show_trace`] is set
+    pub fn update_trace(&mut self) {
+        let selected = self
+            .input_state
+            .selected_soft_body
+            .filter(|&key| self.show_trace && self.soft_bodies.contains_key(key));
+
+        if selected != self.trace_body {
+            self.trace.clear();
+            self.trace_body = selected;
+            self.trace_ticks = 0;
+        }
+
+        let Some(key) = selected else {
+            return;
+        };
+
+        if self.trace_ticks == 0 {
+            if self.trace.len() >= Self::TRACE_LENGTH {
+                self.trace.pop_front();
+            }
+
+            self.trace.push_back(self.soft_bodies[key].centroid());
+        }
+
+        self.trace_ticks = (self.trace_ticks + 1) % Self::TRACE_INTERVAL;
+    }
+
+    /// Removes the oldest debris until there are at most [`Self::max_bodies`] soft bodies, and
+    /// sets [`Self::over_budget`] if that isn't enough
+    pub fn enforce_body_budget(&mut self) {
+        let excess = self.soft_bodies.len().saturating_sub(self.max_bodies);
+
+        if excess > 0 {
+            let mut debris = self
+                .soft_bodies
+                .iter()
+                .filter_map(|(key, soft_body)| Some((key, soft_body.debris_age?)))
+                .collect::<Vec<_>>();
+
+            debris.sort_by(|(_, age_a), (_, age_b)| age_b.total_cmp(age_a));
 
-            i = soft_body.next_point(i);
+            for &(key, _) in debris.iter().take(excess) {
+                self.remove_soft_body(key);
+            }
+
+            self.update_keys();
         }
+
+        self.over_budget = self.soft_bodies.len() > self.max_bodies;
     }
 
-    pub fn destroy_soft_body(&mut self, key: SoftBodyKey, key_index: Option<usize>) {
-        for (index, attatchment_point) in self.soft_bodies[key]
+    /// Disconnects the soft body and removes it without leaving any debris behind. Doesn't update
+    /// [`Self::keys`].
+    pub fn remove_soft_body(&mut self, key: SoftBodyKey) -> Option<SoftBody> {
+        for (index, attatchment_point) in (self
+            .soft_bodies
+            .get(key)?
             .attatchment_points
             .clone()
-            .into_iter()
-            .enumerate()
+            .into_iter())
+        .enumerate()
         {
-            if attatchment_point.connection.is_some() {
-                self.disconnect_attatchment_point(AttatchmentPointHandle {
+            // Disconnecting only fails when a connection doesn't lead back to this seam, which only
+            // happens with inconsistent data, like a seam connected to its own soft body. The seam
+            // is removed along with the soft body anyway, and any constraints left holding its
+            // points drop them in `Constraint::apply_to_soft_bodies` once the soft body is gone.
+            if !attatchment_point.connections.is_empty() {
+                let _ = self.disconnect_attatchment_point(AttatchmentPointHandle {
                     soft_body: key,
                     index,
-                })
-                .unwrap();
+                });
             }
         }
 
-        let soft_body = self.soft_bodies.remove(key).unwrap();
-        if let Some(i) = key_index {
-            self.keys.swap_remove(i);
+        self.soft_bodies.remove(key)
+    }
+
+    pub fn destroy_soft_body(&mut self, key: SoftBodyKey) {
+        self.destroy_soft_bodies(&[key]);
+    }
+
+    /// Disconnects every soft body in `keys` and breaks it into debris, then rebuilds
+    /// [`Self::keys`] once at the end. Keys which have already been removed are skipped.
+    pub fn destroy_soft_bodies(&mut self, keys: &[SoftBodyKey]) {
+        if keys.is_empty() {
+            return;
         }
 
-        for triangle in soft_body.decompose_into_triangles() {
-            let key = self.soft_bodies.insert(triangle);
-            if key_index.is_some() {
-                self.keys.push(key);
+        for &key in keys {
+            let Some(soft_body) = self.remove_soft_body(key) else {
+                continue;
+            };
+
+            for mut triangle in soft_body.decompose_into_triangles() {
+                triangle.update_triangulation_indecies();
+                self.soft_bodies.insert(triangle);
             }
         }
 
-        if key_index.is_none() {
-            self.update_keys();
-        }
+        self.update_keys();
     }
 
     pub fn insert_constraint(&mut self, mut constraint: Constraint) -> ConstraintKey {
@@ -1014,7 +3771,7 @@ impl Simulation {
 
                 for point in points_to_replace {
                     match constraint {
-                        Constraint::HoldTogether { points } => points.push(point),
+                        Constraint::HoldTogether { points, .. } => points.push(point),
                     }
                 }
             }
@@ -1025,63 +3782,162 @@ impl Simulation {
         }
     }
 
+    /// Disconnects every soft body reachable from `soft_body_key` through connected soft bodies,
+    /// returning the source if one was found. Uses an explicit stack and a visited set, so cycles
+    /// and large assemblies can't overflow the stack.
     pub fn clear_connections_from(&mut self, soft_body_key: SoftBodyKey) -> Option<SoftBodyKey> {
-        let soft_body = &mut self.soft_bodies[soft_body_key];
-
         let mut source = None;
 
-        if soft_body.connection_state.is_connected() {
-            if soft_body.connection_state == ConnectionState::Connected {
-                soft_body.connection_state = ConnectionState::Disconnected;
+        let mut visited = HashSet::new();
+        let mut stack = vec![soft_body_key];
+
+        while let Some(key) = stack.pop() {
+            let Some(soft_body) = self.soft_bodies.get_mut(key) else {
+                continue;
+            };
+
+            if !soft_body.connection_state.is_connected() || !visited.insert(key) {
+                continue;
+            }
+
+            match soft_body.connection_state {
+                ConnectionState::Connected => {
+                    soft_body.connection_state = ConnectionState::Disconnected;
+                }
+                ConnectionState::Source => source = Some(key),
+                ConnectionState::Disconnected => (),
+            }
+
+            stack.extend(soft_body.connected_soft_bodies());
+        }
+
+        source
+    }
+
+    /// Connects every disconnected soft body reachable from `soft_body_key`. See
+    /// [`Self::clear_connections_from`].
+    pub fn connect_attatched_soft_bodies(&mut self, soft_body_key: SoftBodyKey) {
+        let mut visited = HashSet::new();
+        let mut stack = vec![soft_body_key];
+
+        while let Some(key) = stack.pop() {
+            let Some(soft_body) = self.soft_bodies.get_mut(key) else {
+                continue;
+            };
+
+            if !matches!(
+                soft_body.connection_state,
+                ConnectionState::Disconnected | ConnectionState::Source,
+            ) || !visited.insert(key)
+            {
+                continue;
+            }
+
+            if soft_body.connection_state == ConnectionState::Disconnected {
+                soft_body.connection_state = ConnectionState::Connected;
             }
 
-            if soft_body.connection_state == ConnectionState::Source {
-                source = Some(soft_body_key);
+            stack.extend(soft_body.connected_soft_bodies());
+        }
+    }
+
+    /// Every soft body reachable from `soft_body_key` through connected attatchment points,
+    /// including itself, regardless of their connection states. Empty if the key is invalid.
+    #[must_use]
+    pub fn connected_component(&self, soft_body_key: SoftBodyKey) -> Vec<SoftBodyKey> {
+        let mut component = Vec::new();
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![soft_body_key];
+
+        while let Some(key) = stack.pop() {
+            let Some(soft_body) = self.soft_bodies.get(key) else {
+                continue;
+            };
+
+            if !visited.insert(key) {
+                continue;
             }
 
-            for attatchment_point in soft_body.attatchment_points.clone() {
-                let Some(AttatchmentPointHandle {
-                    soft_body: connection,
-                    ..
-                }) = attatchment_point.connection
-                else {
-                    continue;
-                };
+            component.push(key);
+            stack.extend(soft_body.connected_soft_bodies());
+        }
+
+        component
+    }
+
+    /// The soft body with [`SoftBody::id`] set to `id`. If a ship was pasted more than once, so
+    /// several share the id, any one of them may be returned.
+    #[must_use]
+    pub fn body_by_id(&self, id: u64) -> Option<SoftBodyKey> {
+        self.soft_bodies
+            .iter()
+            .find(|(_, soft_body)| soft_body.id == Some(id))
+            .map(|(key, _)| key)
+    }
+
+    /// Every constraint holding onto a point of `soft_body_key`
+    pub fn constraints_for_body(
+        &self,
+        soft_body_key: SoftBodyKey,
+    ) -> impl Iterator<Item = ConstraintKey> {
+        self.constraints
+            .iter()
+            .filter(move |(_, constraint)| constraint.involves_soft_body(soft_body_key))
+            .map(|(key, _)| key)
+    }
+
+    /// Returns the largest correction applied by the constraints holding a connected seam
+    /// together during the last tick, or `None` if the seam isn't connected.
+    #[must_use]
+    pub fn seam_stress(&self, handle: AttatchmentPointHandle) -> Option<f32> {
+        let soft_body = self.soft_bodies.get(handle.soft_body)?;
+        let attatchment_point = soft_body.attatchment_points.get(handle.index)?;
+
+        if attatchment_point.connections.is_empty() {
+            return None;
+        }
 
-                let result = self.clear_connections_from(connection);
+        let mut stress = 0.0f32;
 
-                if result.is_some() {
-                    source = result;
-                }
+        for i in soft_body.attatchment_point_indecies(handle.index) {
+            if let Some(constraint) = soft_body.shape[i]
+                .0
+                .constraint
+                .and_then(|constraint| self.constraints.get(constraint))
+            {
+                stress = stress.max(constraint.last_correction());
             }
         }
 
-        source
+        Some(stress)
     }
 
-    pub fn connect_attatched_soft_bodies(&mut self, soft_body_key: SoftBodyKey) {
-        let soft_body = &mut self.soft_bodies[soft_body_key];
+    /// Rigidly rotates the soft body of `handle_a` around its centroid so that its attatchment
+    /// point faces the opposite direction of the one at `handle_b`, as is needed for them to be
+    /// connected.
+    #[must_use]
+    pub fn align_attatchment_points(
+        &mut self,
+        [handle_a, handle_b]: [AttatchmentPointHandle; 2],
+    ) -> Option<()> {
+        let direction_b = self
+            .soft_bodies
+            .get(handle_b.soft_body)?
+            .attatchment_point_direction(handle_b.index)?;
 
-        if matches!(
-            soft_body.connection_state,
-            ConnectionState::Disconnected | ConnectionState::Source,
-        ) {
-            if soft_body.connection_state == ConnectionState::Disconnected {
-                soft_body.connection_state = ConnectionState::Connected;
-            }
+        let soft_body_a = self.soft_bodies.get_mut(handle_a.soft_body)?;
+        let direction_a = soft_body_a.attatchment_point_direction(handle_a.index)?;
 
-            for attatchment_point in soft_body.attatchment_points.clone() {
-                let Some(AttatchmentPointHandle {
-                    soft_body: connection,
-                    ..
-                }) = attatchment_point.connection
-                else {
-                    continue;
-                };
+        let angle = direction_a.angle_between(-direction_b);
 
-                self.connect_attatched_soft_bodies(connection);
-            }
+        if !angle.is_finite() {
+            return None;
         }
+
+        soft_body_a.rotate_around(soft_body_a.centroid(), angle);
+
+        Some(())
     }
 
     #[must_use]
@@ -1096,10 +3952,7 @@ impl Simulation {
         let attatchment_point_a = soft_body_a.attatchment_points.get(handle_a.index)?;
         let attatchment_point_b = soft_body_b.attatchment_points.get(handle_b.index)?;
 
-        if attatchment_point_a.length != attatchment_point_b.length
-            || attatchment_point_a.connection.is_some()
-            || attatchment_point_b.connection.is_some()
-        {
+        if !Self::can_join([handle_a, handle_b], [soft_body_a, soft_body_b]) {
             return None;
         }
 
@@ -1135,19 +3988,79 @@ impl Simulation {
         Some(true)
     }
 
+    /// Whether two attatchment points can be connected to each other: they need the same length,
+    /// room for another connection, and to not already be held together at the same junction,
+    /// whether directly or by both being connected to other seams there
+    fn can_join(
+        [handle_a, handle_b]: [AttatchmentPointHandle; 2],
+        [soft_body_a, soft_body_b]: [&SoftBody; 2],
+    ) -> bool {
+        let (Some(attatchment_point_a), Some(attatchment_point_b)) = (
+            soft_body_a.attatchment_points.get(handle_a.index),
+            soft_body_b.attatchment_points.get(handle_b.index),
+        ) else {
+            return false;
+        };
+
+        // Every point at a junction shares one constraint, so this also catches seams which are
+        // only joined through a third one, which would otherwise be connected twice. Seams which
+        // only share a corner point, like around the middle of a grid, can still be connected.
+        let junction_b = soft_body_b
+            .attatchment_point_indecies(handle_b.index)
+            .filter_map(|index| soft_body_b.shape[index].0.constraint)
+            .collect::<Vec<_>>();
+        let shares_junction = soft_body_a
+            .attatchment_point_indecies(handle_a.index)
+            .all(|index| {
+                soft_body_a.shape[index]
+                    .0
+                    .constraint
+                    .is_some_and(|constraint| junction_b.contains(&constraint))
+            });
+
+        attatchment_point_a.length == attatchment_point_b.length
+            && attatchment_point_a.connections.len() < Self::MAX_SEAM_CONNECTIONS
+            && attatchment_point_b.connections.len() < Self::MAX_SEAM_CONNECTIONS
+            && !attatchment_point_a.connections.contains(&handle_b)
+            && !attatchment_point_b.connections.contains(&handle_a)
+            && !shares_junction
+    }
+
     /// Returns `None` if both handles point to the same soft body, if either is invalid, or if
-    /// they don't have the same length.
+    /// [`Self::can_join`] doesn't allow them to be connected.
     #[must_use]
     pub fn connect_attatchment_points(
         &mut self,
         [handle_a, handle_b]: [AttatchmentPointHandle; 2],
     ) -> Option<()> {
+        if handle_a.soft_body == handle_b.soft_body
+            || !self.soft_bodies.contains_key(handle_a.soft_body)
+            || !self.soft_bodies.contains_key(handle_b.soft_body)
+            || !Self::can_join(
+                [handle_a, handle_b],
+                [handle_a, handle_b].map(|handle| &self.soft_bodies[handle.soft_body]),
+            )
+        {
+            return None;
+        }
+
         if (self.soft_bodies[handle_a.soft_body].connection_state).is_connected() {
             self.connect_attatched_soft_bodies(handle_b.soft_body);
         } else if (self.soft_bodies[handle_b.soft_body].connection_state).is_connected() {
             self.connect_attatched_soft_bodies(handle_a.soft_body);
         }
 
+        // Seams normally face opposite directions, so `point_b` walks backwards. If they face the
+        // same direction, walking backwards would cross every constraint, so walk forwards instead.
+        let same_orientation = match [handle_a, handle_b].map(|handle| {
+            self.soft_bodies
+                .get(handle.soft_body)?
+                .attatchment_point_direction(handle.index)
+        }) {
+            [Some(direction_a), Some(direction_b)] => direction_a.dot(direction_b) > 0.0,
+            _ => false,
+        };
+
         let [soft_body_a, soft_body_b] = self
             .soft_bodies
             .get_disjoint_mut([handle_a.soft_body, handle_b.soft_body])?;
@@ -1158,20 +4071,18 @@ impl Simulation {
         let attatchment_point_a = soft_body_a.attatchment_points.get_mut(handle_a.index)?;
         let attatchment_point_b = soft_body_b.attatchment_points.get_mut(handle_b.index)?;
 
-        if attatchment_point_a.length != attatchment_point_b.length
-            || attatchment_point_a.connection.is_some()
-            || attatchment_point_b.connection.is_some()
-        {
-            return None;
-        }
-
-        // Connect points
-        attatchment_point_a.connection = Some(handle_b);
-        attatchment_point_b.connection = Some(handle_a);
+        // Connect points. If either was already connected, its points already have constraints,
+        // which the new ones are merged into by `insert_constraint`, so every seam at the junction
+        // is held together.
+        attatchment_point_a.connections.push(handle_b);
+        attatchment_point_b.connections.push(handle_a);
 
         let mut point_a = attatchment_point_a.start_point;
-        let mut point_b =
-            (attatchment_point_b.start_point + attatchment_point_b.length - 1) % length_b;
+        let mut point_b = if same_orientation {
+            attatchment_point_b.start_point
+        } else {
+            (attatchment_point_b.start_point + attatchment_point_b.length - 1) % length_b
+        };
 
         let mut new_constraints = Vec::new();
 
@@ -1190,6 +4101,7 @@ impl Simulation {
                         index: point_b,
                     },
                 ],
+                last_correction: 0.0,
             });
 
             if point_a < length_a - 1 {
@@ -1198,81 +4110,98 @@ impl Simulation {
                 point_a = 0;
             }
 
-            if point_b > 0 {
-                point_b -= 1;
+            point_b = if same_orientation {
+                soft_body_b.next_point(point_b)
             } else {
-                point_b = length_b - 1;
-            }
+                soft_body_b.previous_point(point_b)
+            };
         }
 
         for constraint in new_constraints {
             self.insert_constraint(constraint);
         }
 
+        self.emit_seam_particles(handle_a, Self::CONNECT_PARTICLE_COLOR, 3.0);
+
         Some(())
     }
 
+    /// Disconnects `handle_a` from every attatchment point it is connected to. Returns `None`
+    /// without changing anything if it isn't connected, or if any of its connections don't lead
+    /// back to it.
     #[must_use]
     pub fn disconnect_attatchment_point(&mut self, handle_a: AttatchmentPointHandle) -> Option<()> {
-        let source = self.clear_connections_from(handle_a.soft_body);
-
-        let handle_b = &self
+        let connections = self
             .soft_bodies
             .get(handle_a.soft_body)?
             .attatchment_points
             .get(handle_a.index)?
-            .connection?;
-
-        let other_connection = self
-            .soft_bodies
-            .get_mut(handle_b.soft_body)?
-            .attatchment_points
-            .get_mut(handle_b.index)?
-            .connection;
-
-        if other_connection != Some(handle_a) {
+            .connections
+            .clone();
+
+        if connections.is_empty()
+            || !connections.iter().all(|handle_b| {
+                handle_b.soft_body != handle_a.soft_body
+                    && self
+                        .soft_bodies
+                        .get(handle_b.soft_body)
+                        .and_then(|soft_body| soft_body.attatchment_points.get(handle_b.index))
+                        .is_some_and(|attatchment_point| {
+                            attatchment_point.connections.contains(&handle_a)
+                        })
+            })
+        {
             return None;
         }
 
-        let [soft_body_a, soft_body_b] = self
-            .soft_bodies
-            .get_disjoint_mut([handle_a.soft_body, handle_b.soft_body])?;
+        let source = self.clear_connections_from(handle_a.soft_body);
 
-        let length_a = soft_body_a.shape.len();
-        let length_b = soft_body_b.shape.len();
+        for handle_b in connections {
+            let [soft_body_a, soft_body_b] = self
+                .soft_bodies
+                .get_disjoint_mut([handle_a.soft_body, handle_b.soft_body])?;
 
-        let attatchment_point_a = soft_body_a.attatchment_points.get_mut(handle_a.index)?;
-        let attatchment_point_b = soft_body_b.attatchment_points.get_mut(handle_b.index)?;
+            let length_a = soft_body_a.shape.len();
+            let length_b = soft_body_b.shape.len();
 
-        attatchment_point_a.connection = None;
-        attatchment_point_b.connection = None;
+            let attatchment_point_a = &mut soft_body_a.attatchment_points[handle_a.index];
+            let attatchment_point_b = &mut soft_body_b.attatchment_points[handle_b.index];
 
-        let mut point_a = attatchment_point_a.start_point;
-        let mut point_b =
-            (attatchment_point_b.start_point + attatchment_point_b.length - 1) % length_b;
+            attatchment_point_a
+                .connections
+                .retain(|&connection| connection != handle_b);
+            attatchment_point_b
+                .connections
+                .retain(|&connection| connection != handle_a);
 
-        for _ in 0..attatchment_point_a.length {
-            soft_body_a.shape[point_a].0.num_connections -= 1;
-            soft_body_b.shape[point_b].0.num_connections -= 1;
+            let length = attatchment_point_a.length;
+            let mut point_a = attatchment_point_a.start_point;
+            let mut point_b =
+                (attatchment_point_b.start_point + attatchment_point_b.length - 1) % length_b;
 
-            if soft_body_a.shape[point_a].0.num_connections == 0 {
-                soft_body_a.shape[point_a].0.constraint = None;
-            }
+            for _ in 0..length {
+                soft_body_a.shape[point_a].0.num_connections -= 1;
+                soft_body_b.shape[point_b].0.num_connections -= 1;
 
-            if soft_body_b.shape[point_b].0.num_connections == 0 {
-                soft_body_b.shape[point_b].0.constraint = None;
-            }
+                if soft_body_a.shape[point_a].0.num_connections == 0 {
+                    soft_body_a.shape[point_a].0.constraint = None;
+                }
 
-            if point_a < length_a - 1 {
-                point_a += 1;
-            } else {
-                point_a = 0;
-            }
+                if soft_body_b.shape[point_b].0.num_connections == 0 {
+                    soft_body_b.shape[point_b].0.constraint = None;
+                }
 
-            if point_b > 0 {
-                point_b -= 1;
-            } else {
-                point_b = length_b - 1;
+                if point_a < length_a - 1 {
+                    point_a += 1;
+                } else {
+                    point_a = 0;
+                }
+
+                if point_b > 0 {
+                    point_b -= 1;
+                } else {
+                    point_b = length_b - 1;
+                }
             }
         }
 
@@ -1280,6 +4209,612 @@ impl Simulation {
             self.connect_attatched_soft_bodies(source);
         }
 
+        self.emit_seam_particles(handle_a, Self::DISCONNECT_PARTICLE_COLOR, 1.0);
+
         Some(())
     }
+
+    /// Scatters [`Self::SEAM_PARTICLES_PER_POINT`] particles from every point of an attatchment
+    /// point at up to `speed` units per second, carried along with its soft body
+    pub fn emit_seam_particles(
+        &mut self,
+        handle: AttatchmentPointHandle,
+        color: Color,
+        speed: f32,
+    ) {
+        let Some(soft_body) = self.soft_bodies.get(handle.soft_body) else {
+            return;
+        };
+
+        let positions = soft_body
+            .attatchment_point_positions(handle.index)
+            .collect::<Vec<_>>();
+        let velocity = soft_body.velocity();
+
+        self.emit_particles(&positions, velocity, color, speed);
+    }
+
+    /// Scatters [`Self::SEAM_PARTICLES_PER_POINT`] particles from each of `positions` at up to
+    /// `speed` units per second faster than `velocity`
+    fn emit_particles(&mut self, positions: &[Vec2], velocity: Vec2, color: Color, speed: f32) {
+        for &position in positions {
+            for _ in 0..Self::SEAM_PARTICLES_PER_POINT {
+                let start_velocity = velocity
+                    + Vec2::from_angle(rand::gen_range(0.0, TAU))
+                        * rand::gen_range(speed / 3.0, speed);
+
+                self.particles.push(Particle {
+                    position,
+                    shape: Shape::Circle,
+                    age: 0.0,
+                    end_age: rand::gen_range(0.3, 0.6),
+                    start_velocity,
+                    end_velocity: velocity,
+                    start_color: color,
+                    end_color: Color { a: 0.0, ..color },
+                    start_rotation: 0.0,
+                    end_rotation: 0.0,
+                    start_size: rand::gen_range(0.05, 0.1),
+                    end_size: 0.02,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soft_body::SoftBodyBuilder;
+
+    /// A unit square with its bottom left corner at `offset` and a seam of two points along its
+    /// bottom and right edges
+    fn square(offset: Vec2) -> SoftBody {
+        SoftBodyBuilder::default()
+            .offset_ex(offset)
+            .point(0.0, 0.0)
+            .with_attatchment_point(2)
+            .point(1.0, 0.0)
+            .with_attatchment_point(2)
+            .point(1.0, 1.0)
+            .point(0.0, 1.0)
+            .build()
+    }
+
+    fn seam(soft_body: SoftBodyKey, index: usize) -> AttatchmentPointHandle {
+        AttatchmentPointHandle { soft_body, index }
+    }
+
+    /// Four squares, each connected to the next, and the last to the first
+    fn ring() -> (Simulation, Vec<SoftBodyKey>) {
+        let mut simulation = Simulation::new();
+
+        let keys = (0..4)
+            .map(|i| {
+                simulation
+                    .soft_bodies
+                    .insert(square(vec2(i as f32 * 2.0, 0.0)))
+            })
+            .collect::<Vec<_>>();
+
+        for i in 0..keys.len() {
+            let next = keys[(i + 1) % keys.len()];
+
+            simulation
+                .connect_attatchment_points([seam(keys[i], 1), seam(next, 0)])
+                .unwrap();
+        }
+
+        simulation.update_keys();
+
+        (simulation, keys)
+    }
+
+    #[test]
+    fn connection_traversal_handles_rings() {
+        let (mut simulation, keys) = ring();
+
+        assert_eq!(simulation.connected_component(keys[2]).len(), keys.len());
+
+        simulation.soft_bodies[keys[0]].connection_state = ConnectionState::Source;
+        simulation.connect_attatched_soft_bodies(keys[0]);
+
+        for &key in &keys[1..] {
+            assert_eq!(
+                simulation.soft_bodies[key].connection_state,
+                ConnectionState::Connected,
+            );
+        }
+
+        assert_eq!(simulation.clear_connections_from(keys[2]), Some(keys[0]));
+
+        for &key in &keys[1..] {
+            assert_eq!(
+                simulation.soft_bodies[key].connection_state,
+                ConnectionState::Disconnected,
+            );
+        }
+    }
+
+    #[test]
+    fn connection_traversal_skips_missing_soft_bodies() {
+        let (mut simulation, keys) = ring();
+
+        simulation.soft_bodies[keys[0]].connection_state = ConnectionState::Source;
+        simulation.connect_attatched_soft_bodies(keys[0]);
+
+        // Leaves the connections to it dangling
+        simulation.soft_bodies.remove(keys[2]);
+
+        assert_eq!(simulation.clear_connections_from(keys[1]), Some(keys[0]));
+        simulation.connect_attatched_soft_bodies(keys[0]);
+
+        assert_eq!(
+            simulation.connected_component(keys[0]).len(),
+            keys.len() - 1
+        );
+    }
+
+    #[test]
+    fn autopilot_stops_a_spinning_ship() {
+        let mut simulation = Simulation::new();
+
+        // Keeps the reaction wheel from reading the keyboard, which needs a window
+        let released = Keybind {
+            activate: Vec::new(),
+            disable: Vec::new(),
+            forced: Some(false),
+        };
+
+        let mut soft_body = SoftBodyBuilder::default()
+            .point(0.0, 0.0)
+            .point(1.0, 0.0)
+            .point(1.0, 1.0)
+            .point(0.0, 1.0)
+            .with_actor(Actor::ReactionWheel {
+                torque: 20.0,
+                enable_cw: released.clone(),
+                enable_ccw: released,
+            })
+            .connection_state(ConnectionState::Source)
+            .build();
+
+        let center_of_mass = soft_body.calculate_center_of_mass();
+        for (point, _) in &mut soft_body.shape {
+            point.velocity = (point.position - center_of_mass).perp() * 2.0;
+        }
+
+        let key = simulation.soft_bodies.insert(soft_body);
+        let momentum = simulation.soft_bodies[key].velocity();
+
+        let dt = 1.0 / 120.0;
+
+        for _ in 0..120 {
+            simulation.autopilot_hold(key, dt);
+
+            let soft_body = &mut simulation.soft_bodies[key];
+            soft_body.update_bounding_box();
+            let _ = soft_body.apply_impulse_and_velocity(dt, Integrator::default(), &[]);
+        }
+
+        let soft_body = &simulation.soft_bodies[key];
+        assert!(
+            soft_body.angular_velocity().abs() < 1e-3,
+            "angular velocity was {}",
+            soft_body.angular_velocity(),
+        );
+        assert!(soft_body.velocity().distance(momentum) < 1e-3);
+    }
+
+    #[test]
+    fn focused_text_field_consumes_keys_until_released() {
+        let mut input_state = InputState::default();
+        let held = |key_code| key_code == KeyCode::W;
+
+        input_state.update_suppressed_keys(Some(KeyCode::W), held);
+        assert!(input_state.suppressed_keys.is_empty());
+
+        input_state.typing = true;
+        input_state.update_suppressed_keys(Some(KeyCode::W), held);
+        assert_eq!(input_state.suppressed_keys, [KeyCode::W]);
+
+        // Still held after the text field loses focus, so it still shouldn't fire anything
+        input_state.typing = false;
+        input_state.update_suppressed_keys(None, held);
+        assert_eq!(input_state.suppressed_keys, [KeyCode::W]);
+
+        let thrust = Keybind {
+            activate: vec![KeyCode::W],
+            disable: Vec::new(),
+            forced: None,
+        };
+        assert!(!thrust.activate_down(&input_state.suppressed_keys));
+
+        input_state.update_suppressed_keys(None, |_| false);
+        assert!(input_state.suppressed_keys.is_empty());
+    }
+
+    #[test]
+    fn seams_facing_the_same_way_connect_without_crossing() {
+        let mut simulation = Simulation::new();
+
+        let key_a = simulation.soft_bodies.insert(square(Vec2::ZERO));
+
+        // Wound clockwise, so its left seam runs upwards like the right seam of `key_a`
+        let key_b = simulation.soft_bodies.insert(
+            SoftBodyBuilder::default()
+                .point(2.0, 0.0)
+                .with_attatchment_point(2)
+                .point(2.0, 1.0)
+                .point(3.0, 1.0)
+                .point(3.0, 0.0)
+                .build(),
+        );
+
+        let [direction_a, direction_b] = [(key_a, 1), (key_b, 0)].map(|(key, index)| {
+            simulation.soft_bodies[key]
+                .attatchment_point_direction(index)
+                .unwrap()
+        });
+        assert!(direction_a.dot(direction_b) > 0.0);
+
+        simulation
+            .connect_attatchment_points([seam(key_a, 1), seam(key_b, 0)])
+            .unwrap();
+
+        assert_eq!(simulation.constraints.len(), 2);
+
+        for constraint in simulation.constraints.values() {
+            let Constraint::HoldTogether { points, .. } = constraint;
+
+            let [a, b] = [points[0], points[1]].map(|point| {
+                simulation.soft_bodies[point.soft_body].shape[point.index]
+                    .0
+                    .position
+            });
+
+            assert_eq!(a.y, b.y, "{a} was held to {b}");
+        }
+    }
+
+    #[test]
+    fn massless_points_dont_poison_connected_soft_bodies() {
+        let mut simulation = Simulation::new();
+
+        let weightless = SoftBodyBuilder::default()
+            .mass(0.0)
+            .offset_ex(vec2(1.0, 0.0))
+            .point(0.0, 0.0)
+            .point(1.0, 0.0)
+            .point(1.0, 1.0)
+            .point(0.0, 1.0)
+            .with_attatchment_point(2)
+            .build();
+
+        assert!(
+            weightless
+                .shape
+                .iter()
+                .all(|(point, _)| point.mass == Point::MINIMUM_MASS),
+            "masses weren't clamped",
+        );
+
+        let key_a = simulation.soft_bodies.insert(square(Vec2::ZERO));
+        let key_b = simulation.soft_bodies.insert(weightless);
+        simulation
+            .connect_attatchment_points([seam(key_a, 1), seam(key_b, 0)])
+            .unwrap();
+
+        // Thrown into the weightless body
+        let mut projectile = square(vec2(2.5, 0.25));
+        for (point, _) in &mut projectile.shape {
+            point.velocity = vec2(-20.0, 0.0);
+        }
+        simulation.soft_bodies.insert(projectile);
+
+        simulation.update_keys();
+
+        for _ in 0..120 {
+            simulation.tick_simulation(1.0 / 120.0);
+        }
+
+        for soft_body in simulation.soft_bodies.values() {
+            for (point, _) in &soft_body.shape {
+                assert!(point.position.is_finite() && point.velocity.is_finite());
+            }
+        }
+    }
+
+    /// The centroids of two squares stacked on either side of a wider rectangle, overlapping it a
+    /// little, after letting them push each other apart for a second. Collisions are checked in
+    /// the opposite order when `reversed` is set.
+    fn settle_stack(reversed: bool) -> Vec<Vec2> {
+        let mut simulation = Simulation::new();
+
+        let middle = SoftBodyBuilder::default()
+            .point(-0.5, 0.9)
+            .point(1.5, 0.9)
+            .point(1.5, 1.9)
+            .point(-0.5, 1.9)
+            .build();
+
+        let mut keys = vec![
+            simulation.soft_bodies.insert(square(Vec2::ZERO)),
+            simulation.soft_bodies.insert(middle),
+            simulation.soft_bodies.insert(square(vec2(0.0, 1.8))),
+        ];
+
+        if reversed {
+            keys.reverse();
+        }
+
+        simulation.keys = keys.clone();
+
+        for _ in 0..120 {
+            simulation.tick_simulation(1.0 / 120.0);
+        }
+
+        if reversed {
+            keys.reverse();
+        }
+
+        keys.iter()
+            .map(|&key| simulation.soft_bodies[key].calculate_centroid())
+            .collect()
+    }
+
+    #[test]
+    fn stacked_soft_bodies_settle_symmetrically() {
+        let forwards = settle_stack(false);
+        let backwards = settle_stack(true);
+
+        for (a, b) in forwards.iter().zip(&backwards) {
+            assert!(a.distance(*b) < 1e-4, "{a} settled differently to {b}");
+        }
+
+        let [bottom, middle, top] = forwards.as_slice() else {
+            unreachable!();
+        };
+
+        assert!(
+            middle.distance(vec2(0.5, 1.4)) < 1e-3,
+            "middle settled at {middle}"
+        );
+        assert!(
+            (bottom.y + top.y - 2.0 * middle.y).abs() < 1e-3,
+            "bottom settled at {bottom} and top at {top}",
+        );
+        assert!(top.y - bottom.y > 1.8, "the squares didn't separate");
+    }
+
+    fn kinetic_energy(simulation: &Simulation) -> f32 {
+        simulation
+            .soft_bodies
+            .values()
+            .flat_map(|soft_body| &soft_body.shape)
+            .map(|(point, _)| 0.5 * point.mass * point.velocity.length_squared())
+            .sum()
+    }
+
+    #[test]
+    fn pressing_soft_bodies_together_doesnt_add_energy() {
+        let mut simulation = Simulation::new();
+
+        simulation.soft_bodies.insert(square(Vec2::ZERO));
+
+        // Fast enough to be substepped into the still square
+        let mut projectile = square(vec2(1.5, 0.0));
+        for (point, _) in &mut projectile.shape {
+            point.velocity = vec2(-30.0, 0.0);
+        }
+        simulation.soft_bodies.insert(projectile);
+
+        simulation.update_keys();
+
+        let before = kinetic_energy(&simulation);
+        let mut most = before;
+
+        for _ in 0..60 {
+            simulation.tick_simulation(1.0 / 120.0);
+
+            most = most.max(kinetic_energy(&simulation));
+        }
+
+        assert!(
+            most <= before * 1.001,
+            "energy rose from {before} to {most}"
+        );
+        assert!(
+            kinetic_energy(&simulation) < before,
+            "the soft bodies never collided",
+        );
+    }
+
+    #[test]
+    fn seams_wrapping_past_the_last_point_connect() {
+        let mut simulation = Simulation::new();
+
+        // Both seams start at the last point and wrap around to the first
+        let key_a = simulation.soft_bodies.insert(
+            SoftBodyBuilder::default()
+                .point(0.0, 0.0)
+                .point(1.0, 0.0)
+                .point(1.0, 1.0)
+                .point(0.0, 1.0)
+                .with_attatchment_point(2)
+                .build(),
+        );
+        let key_b = simulation.soft_bodies.insert(
+            SoftBodyBuilder::default()
+                .point(-0.1, 1.0)
+                .point(-1.1, 1.0)
+                .point(-1.1, 0.0)
+                .point(-0.1, 0.0)
+                .with_attatchment_point(2)
+                .build(),
+        );
+
+        let handles = [seam(key_a, 0), seam(key_b, 0)];
+
+        assert_eq!(
+            simulation.are_attatchment_points_within_range(handles, 0.2),
+            Some(true),
+        );
+
+        simulation.connect_attatchment_points(handles).unwrap();
+
+        assert_eq!(simulation.constraints.len(), 2);
+
+        for constraint in simulation.constraints.values() {
+            let Constraint::HoldTogether { points, .. } = constraint;
+
+            let [a, b] = [points[0], points[1]].map(|point| {
+                simulation.soft_bodies[point.soft_body].shape[point.index]
+                    .0
+                    .position
+            });
+
+            assert_eq!(a.y, b.y, "{a} was held to {b}");
+        }
+
+        for key in [key_a, key_b] {
+            let num_connections = simulation.soft_bodies[key]
+                .shape
+                .iter()
+                .map(|(point, _)| point.num_connections)
+                .collect::<Vec<_>>();
+
+            assert_eq!(num_connections, [1, 0, 0, 1]);
+        }
+    }
+
+    #[test]
+    fn naming_the_same_soft_body_twice_does_nothing() {
+        let mut simulation = Simulation::new();
+
+        let key = simulation.soft_bodies.insert(square(Vec2::ZERO));
+        let handles = [seam(key, 0), seam(key, 1)];
+
+        assert_eq!(simulation.connect_attatchment_points(handles), None);
+        assert!(simulation.constraints.is_empty());
+
+        let before = simulation.soft_bodies[key].shape.clone();
+        simulation.push_together(handles, 1.0 / 120.0);
+
+        for ((point, _), (before, _)) in simulation.soft_bodies[key].shape.iter().zip(&before) {
+            assert_eq!(point.position, before.position);
+        }
+
+        // Collisions between a soft body and itself are skipped
+        simulation.keys = vec![key, key];
+        simulation.tick_simulation(1.0 / 120.0);
+
+        // Like a malformed save which connects a soft body to itself
+        let attatchment_points = &mut simulation.soft_bodies[key].attatchment_points;
+        attatchment_points[0].connections.push(handles[1]);
+        attatchment_points[1].connections.push(handles[0]);
+
+        assert_eq!(simulation.disconnect_attatchment_point(handles[0]), None);
+        assert!(simulation.remove_soft_body(key).is_some());
+        assert!(simulation.soft_bodies.is_empty());
+    }
+
+    #[test]
+    fn seams_joined_through_a_third_cant_be_connected_again() {
+        let mut simulation = Simulation::new();
+
+        let keys = (0..3)
+            .map(|i| {
+                simulation
+                    .soft_bodies
+                    .insert(square(vec2(i as f32 * 2.0, 0.0)))
+            })
+            .collect::<Vec<_>>();
+        let [a, b, c] = [seam(keys[0], 1), seam(keys[1], 0), seam(keys[2], 0)];
+
+        simulation.connect_attatchment_points([a, b]).unwrap();
+        simulation.connect_attatchment_points([b, c]).unwrap();
+
+        assert_eq!(simulation.connect_attatchment_points([a, c]), None);
+        assert_eq!(simulation.connect_attatchment_points([c, a]), None);
+        assert_eq!(
+            simulation.are_attatchment_points_within_range([a, c], f32::INFINITY),
+            None
+        );
+
+        for handle in [a, c] {
+            let soft_body = &simulation.soft_bodies[handle.soft_body];
+
+            assert_eq!(
+                soft_body.attatchment_points[handle.index].connections.len(),
+                1
+            );
+
+            for index in soft_body.attatchment_point_indecies(handle.index) {
+                assert_eq!(soft_body.shape[index].0.num_connections, 1);
+            }
+        }
+
+        // All three seams are held together by one constraint per point
+        assert_eq!(simulation.constraints.len(), 2);
+    }
+
+    #[test]
+    fn soft_bodies_are_damaged_once_per_colliding_pair() {
+        let mut simulation = Simulation::new();
+
+        let mut still = square(Vec2::ZERO);
+        still.health = 1000.0;
+        let still = simulation.soft_bodies.insert(still);
+
+        // Slow enough to only be checked once per tick, already pressed into the still square
+        let mut moving = square(vec2(0.9, 0.5));
+        moving.health = 1000.0;
+        for (point, _) in &mut moving.shape {
+            point.velocity = vec2(-5.0, 0.0);
+        }
+        let moving = simulation.soft_bodies.insert(moving);
+
+        simulation.update_keys();
+        simulation.tick_simulation(1.0 / 120.0);
+
+        let strongest = simulation
+            .drain_collision_events()
+            .map(|event| event.impulse)
+            .fold(0.0, f32::max);
+        let expected = 1000.0 - (strongest - SoftBody::MINIMUM_DAMAGE_IMPULSE);
+
+        assert!(strongest > SoftBody::MINIMUM_DAMAGE_IMPULSE, "{strongest}");
+        for key in [still, moving] {
+            let health = simulation.soft_bodies[key].health;
+            assert!(
+                (health - expected).abs() < 1e-3,
+                "health is {health}, expected {expected}",
+            );
+        }
+    }
+
+    #[test]
+    fn refuel_zones_leave_their_trigger_events_queued() {
+        let mut simulation = Simulation::new();
+
+        simulation.triggers.push(Trigger::refuel_zone(
+            BoundingBox::fit_points(&[vec2(-10.0, -10.0), vec2(10.0, 10.0)]),
+            1.0,
+        ));
+        let key = simulation
+            .soft_bodies
+            .insert(prefabs::habitat_bubble(Vec2::ZERO).build());
+
+        simulation.update_keys();
+        simulation.tick_simulation(1.0 / 120.0);
+
+        let events = simulation.drain_trigger_events().collect::<Vec<_>>();
+
+        assert!(matches!(
+            events[..],
+            [TriggerEvent::Enter { trigger: 0, soft_body }] if soft_body == key,
+        ));
+        assert!(!simulation.particles.is_empty());
+    }
 }
@@ -10,13 +10,15 @@ use macroquad::{
     input::{self, KeyCode},
     math::{Vec2, vec2},
     models::{self, Mesh},
-    shapes,
+    rand, shapes, time,
     ui::Vertex,
 };
+use nalgebra::Vector2;
 
 use crate::{
     particle::{Particle, Shape},
-    simulation::{ConstraintKey, KeybindFocus, SoftBodyKey},
+    simulation::{CollisionEvent, ConstraintKey, KeybindFocus, SoftBodyKey},
+    stars::AddOffset,
     utils,
 };
 
@@ -29,16 +31,52 @@ pub struct SoftBody {
     pub shape: Vec<(Point, Line)>,
     pub internal_springs: Vec<([usize; 2], LinearSpring)>,
     pub bounding_box: BoundingBox,
+    /// The furthest any point is from the centroid. Cached by [`Self::update_bounding_box`]. See
+    /// [`Self::bounding_circle`]
+    pub bounding_radius: f32,
+    /// Cached by [`Self::update_bounding_box`]. See [`Self::centroid`]
+    pub cached_centroid: Vec2,
+    /// Cached by [`Self::update_bounding_box`]. See [`Self::center_of_mass`]
+    pub cached_center_of_mass: Vec2,
     pub gas_force: f32,
     pub pressure: f32,
 
     pub triangle_indices: Vec<u16>,
+    /// The number of points in the shape when `triangle_indices` was last calculated
+    pub triangulated_points: Option<usize>,
     pub debris_age: Option<f32>,
+    /// How many ticks in a row the shape has crossed over itself
+    pub folded_ticks: usize,
+    /// The position of each point before it was last moved by
+    /// [`Self::apply_impulse_and_velocity`]
+    pub previous_positions: Vec<Vec2>,
 
     pub attatchment_points: Vec<AttatchmentPoint>,
     pub actors: Vec<Actor>,
 
     pub connection_state: ConnectionState,
+    /// Set by the simulation when the habitat bubbles this is connected to have no fuel left, which
+    /// stops its rocket motors
+    pub out_of_fuel: bool,
+
+    /// The layers this body is on
+    pub collision_layer: u32,
+    /// The layers this body collides with. See [`Self::collides_with`]
+    pub collision_mask: u32,
+
+    /// Reduced once per tick by the strongest collision with each other soft body, if its impulse
+    /// is above [`Self::MINIMUM_DAMAGE_IMPULSE`]. The simulation destroys the shape once this
+    /// reaches zero. Infinite by default.
+    pub health: f32,
+
+    /// Holds every point still, and makes collisions and connections treat the shape as if it
+    /// had infinite mass
+    pub frozen: bool,
+
+    /// A name for the soft body which stays the same when it is saved and loaded, unlike its
+    /// [`SoftBodyKey`], so scenarios and scripts can refer to it. See
+    /// [`Simulation::body_by_id`](crate::simulation::Simulation::body_by_id)
+    pub id: Option<u64>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -79,18 +117,44 @@ impl SoftBody {
         damping: 10.0,
         compression: true,
         tension: true,
-        maximum_force: 100.0,
+        maximum_force: 2500.0,
         maximum_damping: 100.0,
         destroy_on_maximum: false,
+        minimum_length: 0.0,
     };
 
+    pub const MINIMUM_COLLISION_EVENT_IMPULSE: f32 = 0.1;
+    /// Collisions take away as much health as their impulse exceeds this by
+    pub const MINIMUM_DAMAGE_IMPULSE: f32 = 1.0;
+    pub const HEALTH_TEXT_SIZE: f32 = 0.3;
+
+    /// The acceleration of the points of edges which cross each other, along their outward normals
+    pub const FOLD_SEPARATION_ACCELERATION: f32 = 200.0;
+
     pub const FILL_COLOR: Color = colors::LIGHTGRAY;
     pub const OUTLINE_COLOR: Color = colors::GRAY;
+    /// The outline of [`Self::frozen`] soft bodies
+    pub const FROZEN_OUTLINE_COLOR: Color = colors::SKYBLUE;
+    /// The color of [`Actor::Label`]s
+    pub const LABEL_COLOR: Color = colors::WHITE;
 
     pub const ATTATCHMENT_POINT_PADDING: f32 = 0.25;
 
+    /// See [`Self::draw_velocities`]
+    pub const VELOCITY_ARROW_THICKNESS: f32 = 0.02;
+    /// How long the arrow showing the target direction of an [`Actor::OrientationSpring`] is
+    pub const ORIENTATION_ARROW_LENGTH: f32 = 0.5;
+
     pub const ATTATCHMENT_POINT_THICKNESS: f32 = 0.05;
     pub const ATTATCHMENT_POINT_THICKNESS_SELECTED: f32 = 0.1;
+    /// See [`Self::draw_attatchment_point_length`]
+    pub const ATTATCHMENT_POINT_LABEL_SIZE: f32 = 0.25;
+    /// How far outside the shape the length of a seam is written
+    pub const ATTATCHMENT_POINT_LABEL_OFFSET: f32 = 0.2;
+
+    /// How far outside of its bounding box a soft body's outline and attatchment points can be
+    /// drawn
+    pub const DRAW_MARGIN: f32 = Self::ATTATCHMENT_POINT_THICKNESS_SELECTED;
 
     pub const ATTATCHMENT_POINT_COLOR: Color = colors::WHITE;
     pub const ATTATCHMENT_POINT_COLOR_USED: Color =
@@ -105,25 +169,79 @@ impl SoftBody {
             shape,
             internal_springs,
             bounding_box: BoundingBox::default(),
+            bounding_radius: 0.0,
+            cached_centroid: Vec2::ZERO,
+            cached_center_of_mass: Vec2::ZERO,
             gas_force,
             pressure: 0.0,
 
             triangle_indices: Vec::new(),
+            triangulated_points: None,
             debris_age: None,
+            folded_ticks: 0,
+            previous_positions: Vec::new(),
 
             attatchment_points: Vec::new(),
             actors: Vec::new(),
 
             connection_state: ConnectionState::Disconnected,
+            out_of_fuel: false,
+
+            collision_layer: u32::MAX,
+            collision_mask: u32::MAX,
+
+            health: f32::INFINITY,
+
+            frozen: false,
+
+            id: None,
         };
 
         soft_body.update_triangulation_indecies();
+        soft_body.update_bounding_box();
         soft_body
     }
 
+    /// The soft bodies connected to each of this one's attatchment points
+    pub fn connected_soft_bodies(&self) -> impl Iterator<Item = SoftBodyKey> {
+        self.attatchment_points
+            .iter()
+            .flat_map(|attatchment_point| &attatchment_point.connections)
+            .map(|connection| connection.soft_body)
+    }
+
+    /// Two bodies collide only if each one's mask includes a layer of the other
+    pub fn collides_with(&self, other: &SoftBody) -> bool {
+        self.collision_mask & other.collision_layer != 0
+            && other.collision_mask & self.collision_layer != 0
+    }
+
+    /// A circle around the centroid containing every point. Checked after the bounding box, since
+    /// it rules out pairs of round shapes whose bounding boxes only overlap at the corners.
+    pub fn bounding_circle(&self) -> BoundingCircle {
+        BoundingCircle {
+            center: self.cached_centroid,
+            radius: self.bounding_radius,
+        }
+    }
+
+    /// Whether anything drawn for this soft body could be within `view`. Actors can draw past
+    /// the ends of an edge, so the margin grows with the size of the soft body.
+    pub fn is_visible_in(&self, view: &BoundingBox) -> bool {
+        view.is_other_within_distance(
+            &self.bounding_box,
+            Self::DRAW_MARGIN + self.bounding_box.size.max_element(),
+        )
+    }
+
     pub fn draw(&self) {
         self.fill_color(Self::FILL_COLOR);
-        self.outline_color(0.025, Self::OUTLINE_COLOR);
+
+        if self.frozen {
+            self.outline_color(0.05, Self::FROZEN_OUTLINE_COLOR);
+        } else {
+            self.outline_color(0.025, Self::OUTLINE_COLOR);
+        }
     }
 
     pub fn draw_attatchment_points(&self) {
@@ -132,17 +250,32 @@ impl SoftBody {
         }
     }
 
+    /// Writes the number of points in the seam just outside its middle point
+    pub fn draw_attatchment_point_length(&self, index: usize) {
+        let attatchment_point = &self.attatchment_points[index];
+
+        let middle =
+            (attatchment_point.start_point + attatchment_point.length / 2) % self.shape.len();
+        let position = self.shape[middle].0.position;
+        let outwards = (position - self.center_of_mass()).normalize_or_zero();
+
+        utils::draw_world_text(
+            &attatchment_point.length.to_string(),
+            position + outwards * Self::ATTATCHMENT_POINT_LABEL_OFFSET,
+            Self::ATTATCHMENT_POINT_LABEL_SIZE,
+            Self::ATTATCHMENT_POINT_COLOR,
+        );
+    }
+
     pub fn draw_attatchment_point(&self, index: usize, selected: bool, force_color: Option<Color>) {
-        let attatchment_point = self.attatchment_points[index];
+        let attatchment_point = &self.attatchment_points[index];
 
         let mut i = attatchment_point.start_point;
 
-        let color = force_color.unwrap_or_else(|| {
-            if attatchment_point.connection.is_some() {
-                Self::ATTATCHMENT_POINT_COLOR_USED
-            } else {
-                Self::ATTATCHMENT_POINT_COLOR
-            }
+        let color = force_color.unwrap_or(if attatchment_point.connections.is_empty() {
+            Self::ATTATCHMENT_POINT_COLOR
+        } else {
+            Self::ATTATCHMENT_POINT_COLOR_USED
         });
 
         let thickness = if selected {
@@ -222,26 +355,33 @@ impl SoftBody {
     }
 
     pub fn outline_color(&self, thickness: f32, color: Color) {
-        for i in 0..self.shape.len() {
-            let (point_a, _, point_b) = self.get_line(i).unwrap();
-
+        for (point_a, _, point_b) in self.edges() {
             utils::draw_line(point_a.position, point_b.position, thickness, color);
         }
     }
 
-    pub fn draw_actors_back(&self) {
+    pub fn draw_actors_back(&self, suppressed_keys: &[KeyCode]) {
         for actor in &self.actors {
             match actor {
-                Actor::RocketMotor { line, enable, .. } => {
+                Actor::RocketMotor {
+                    line,
+                    enable,
+                    exhaust_start,
+                    exhaust_end,
+                    ..
+                } => {
                     let (point_a, _, point_b) = self.get_line(*line).unwrap();
                     utils::draw_line(
                         point_a.position.lerp(point_b.position, -0.9),
                         point_b.position.lerp(point_a.position, -0.9),
                         0.2,
-                        if enable.is_down() && self.connection_state.is_connected() {
+                        if enable.is_down(suppressed_keys) && self.thrust_available() {
                             utils::color_lerp(
-                                colors::RED,
-                                colors::YELLOW,
+                                Color {
+                                    a: 1.0,
+                                    ..*exhaust_end
+                                },
+                                *exhaust_start,
                                 macroquad::rand::gen_range(0.0, 1.0),
                             )
                         } else {
@@ -251,15 +391,20 @@ impl SoftBody {
                 }
                 Actor::HabitatBubble { .. } => (),
                 Actor::Piston { .. } => (),
+                Actor::ReactionWheel { .. } => (),
+                Actor::OrientationSpring { .. } => (),
+                Actor::Label { .. } => (),
             }
         }
     }
 
-    pub fn draw_actors_front(&self) {
+    pub fn draw_actors_front(&self, suppressed_keys: &[KeyCode]) {
         for actor in &self.actors {
             match actor {
                 Actor::RocketMotor { .. } => {}
-                Actor::HabitatBubble { minimum_pressure } => {
+                Actor::HabitatBubble {
+                    minimum_pressure, ..
+                } => {
                     let center = self.center_of_mass();
 
                     shapes::draw_circle(
@@ -285,7 +430,9 @@ impl SoftBody {
                             point_a.position.lerp(point_b.position, 0.2),
                             point_b.position.lerp(point_a.position, 0.2),
                             0.1,
-                            if enable.is_down() && self.connection_state.is_connected() {
+                            if enable.is_down(suppressed_keys)
+                                && self.connection_state.is_connected()
+                            {
                                 colors::GREEN
                             } else {
                                 colors::RED
@@ -293,15 +440,77 @@ impl SoftBody {
                         );
                     }
                 }
+                Actor::ReactionWheel {
+                    enable_cw,
+                    enable_ccw,
+                    ..
+                } => {
+                    let center = self.center_of_mass();
+                    let spinning = (enable_cw.is_down(suppressed_keys)
+                        != enable_ccw.is_down(suppressed_keys))
+                        && self.connection_state.is_connected();
+
+                    shapes::draw_circle_lines(
+                        center.x,
+                        center.y,
+                        0.25,
+                        0.1,
+                        if spinning { colors::GREEN } else { colors::RED },
+                    );
+                }
+                Actor::OrientationSpring {
+                    line,
+                    target_direction,
+                    ..
+                } => {
+                    let (&Point { position: a, .. }, _, &Point { position: b, .. }) =
+                        self.get_line(*line).unwrap();
+                    let center = (a + b) / 2.0;
+
+                    utils::draw_arrow(
+                        center,
+                        center + *target_direction * Self::ORIENTATION_ARROW_LENGTH,
+                        Self::VELOCITY_ARROW_THICKNESS,
+                        colors::SKYBLUE,
+                    );
+                }
+                Actor::Label { offset, text, size } => {
+                    utils::draw_world_text(
+                        text,
+                        self.center_of_mass() + *offset,
+                        *size,
+                        Self::LABEL_COLOR,
+                    );
+                }
             }
         }
     }
 
-    pub fn draw_springs(&self) {
+    /// Fills the shape by its pressure relative to `maximum_pressure`, and draws every edge and
+    /// internal spring by how close its force is to its maximum
+    pub fn draw_stress_map(&self, maximum_pressure: f32) {
+        self.fill_color(Color {
+            a: 0.5,
+            ..utils::generate_color_for_load(self.pressure / maximum_pressure)
+        });
+
         if self.shape.len() > 1 {
-            for i in 0..self.shape.len() {
-                let (point_a, line, point_b) = self.get_line(i).unwrap();
+            for (point_a, line, point_b) in self.edges() {
+                line.spring.draw_load(point_a, point_b);
+            }
+
+            for &(indecies, ref spring) in &self.internal_springs {
+                let (point_a, _) = &self.shape[indecies[0]];
+                let (point_b, _) = &self.shape[indecies[1]];
+
+                spring.draw_load(point_a, point_b);
+            }
+        }
+    }
 
+    pub fn draw_springs(&self) {
+        if self.shape.len() > 1 {
+            for (point_a, line, point_b) in self.edges() {
                 line.spring.draw_line(point_a, point_b);
             }
 
@@ -312,9 +521,7 @@ impl SoftBody {
                 spring.draw_line(point_a, point_b);
             }
 
-            for i in 0..self.shape.len() {
-                let [point_a, point_b, point_c] = self.get_angle(i).unwrap();
-
+            for [point_a, point_b, point_c] in self.angles() {
                 if let Some(spring) = point_b.spring {
                     spring.draw_circle(point_a, point_b, point_c);
                 }
@@ -322,10 +529,44 @@ impl SoftBody {
         }
     }
 
+    /// Draws an arrow from every point along its velocity, `scale` units long per unit of speed
+    pub fn draw_velocities(&self, scale: f32) {
+        for (point, _) in &self.shape {
+            let speed = point.velocity.length();
+
+            if speed > f32::EPSILON {
+                utils::draw_arrow(
+                    point.position,
+                    point.position + point.velocity * scale,
+                    Self::VELOCITY_ARROW_THICKNESS,
+                    utils::generate_color_for_spring(speed, 0.0),
+                );
+            }
+        }
+    }
+
     #[must_use]
-    pub fn apply_impulse_and_velocity(&mut self, dt: f32) -> (Option<Vec2>, Vec<Particle>, bool) {
+    pub fn apply_impulse_and_velocity(
+        &mut self,
+        dt: f32,
+        integrator: Integrator,
+        suppressed_keys: &[KeyCode],
+    ) -> (Option<Vec2>, Vec<Particle>, bool) {
         let mut maximum_reached = false;
-        let (new_camera_position, new_particles) = self.update_actors(dt);
+        let (new_camera_position, new_particles) = self.update_actors(dt, suppressed_keys);
+
+        if self.frozen {
+            self.previous_positions.clear();
+
+            for (point, _) in &mut self.shape {
+                point.impulse = Vec2::ZERO;
+                point.velocity = Vec2::ZERO;
+
+                self.previous_positions.push(point.position);
+            }
+
+            return (new_camera_position, new_particles, maximum_reached);
+        }
 
         self.add_pressure_impulse(dt);
 
@@ -343,7 +584,9 @@ impl SoftBody {
         if self.shape.len() > 1 {
             // Angular Springs
             for i in 0..self.shape.len() {
-                let [point_a, point_b, point_c] = self.get_angle_mut(i).unwrap();
+                let Some([point_a, point_b, point_c]) = self.get_angle_mut(i) else {
+                    break;
+                };
 
                 if let Some(spring) = point_b.spring {
                     spring.apply_forces(point_a, point_b, point_c, dt);
@@ -365,17 +608,48 @@ impl SoftBody {
             }
         }
 
+        self.previous_positions.clear();
+        self.previous_positions
+            .extend(self.shape.iter().map(|(point, _)| point.position));
+
         for (point, _) in &mut self.shape {
             point.impulse -= point.velocity * Self::DRAG * dt;
 
-            point.apply_impulse_and_velocity(dt);
+            point.apply_impulse_and_velocity(dt, integrator);
         }
 
+        self.enforce_minimum_lengths();
+
         self.update_bounding_box();
 
         (new_camera_position, new_particles, maximum_reached)
     }
 
+    /// See [`LinearSpring::minimum_length`]. Must be called after [`Self::previous_positions`] is
+    /// set for the tick.
+    pub fn enforce_minimum_lengths(&mut self) {
+        if self.shape.len() < 2 || self.previous_positions.len() != self.shape.len() {
+            return;
+        }
+
+        for i in 0..self.shape.len() {
+            let next = (i + 1) % self.shape.len();
+            let previous_displacement = self.previous_positions[i] - self.previous_positions[next];
+
+            let (point_a, line, point_b) = self.get_line_mut(i).unwrap();
+
+            line.spring
+                .enforce_minimum_length(point_a, point_b, previous_displacement);
+        }
+
+        for &([a, b], ref spring) in &self.internal_springs {
+            let previous_displacement = self.previous_positions[a] - self.previous_positions[b];
+            let [(point_a, _), (point_b, _)] = self.shape.get_disjoint_mut([a, b]).unwrap();
+
+            spring.enforce_minimum_length(point_a, point_b, previous_displacement);
+        }
+    }
+
     pub fn add_pressure_impulse(&mut self, dt: f32) {
         if self.gas_force.abs() <= f32::EPSILON {
             self.pressure = 0.0;
@@ -387,24 +661,30 @@ impl SoftBody {
 
         self.pressure = pressure;
 
-        for i in 0..self.shape.len() {
-            let (point_a, _, point_b) = self.get_line_mut(i).unwrap();
-
-            // Magnitude is proportional to the length of the edge
-            let force_direction = (point_a.position - point_b.position).perp();
-
-            let pressure_force = force_direction * pressure;
+        // Each point takes half the force on both of its edges, which is proportional to their
+        // length, and together point away from the line between its neighbours
+        let impulses = self
+            .angles()
+            .map(|[point_a, _, point_c]| {
+                (point_a.position - point_c.position).perp() * pressure * dt / 2.0
+            })
+            .collect::<Vec<_>>();
 
-            point_a.impulse += pressure_force * dt / 2.0;
-            point_b.impulse += pressure_force * dt / 2.0;
+        for ((point, _), impulse) in self.shape.iter_mut().zip(impulses) {
+            point.impulse += impulse;
         }
     }
 
-    pub fn update_actors(&mut self, dt: f32) -> (Option<Vec2>, Vec<Particle>) {
+    pub fn update_actors(
+        &mut self,
+        dt: f32,
+        suppressed_keys: &[KeyCode],
+    ) -> (Option<Vec2>, Vec<Particle>) {
         let mut new_camera_position = None;
         let mut new_particles = Vec::new();
 
         let center_of_mass = self.center_of_mass();
+        let mut total_torque = 0.0;
 
         for actor in &mut self.actors {
             match actor {
@@ -414,8 +694,23 @@ impl SoftBody {
                     enable,
                     particle_time,
                     max_particle_time,
+                    gimbal,
+                    exhaust_start,
+                    exhaust_end,
                 } => {
-                    if enable.is_down() && self.connection_state.is_connected() {
+                    let mut force = *force;
+
+                    if let Some(gimbal) = gimbal {
+                        gimbal.update(dt, suppressed_keys);
+
+                        force = Vec2::from_angle(gimbal.angle).rotate(force);
+                    }
+
+                    // Same as `Self::thrust_available`, which can't borrow all of `self` here
+                    if enable.is_down(suppressed_keys)
+                        && self.connection_state.is_connected()
+                        && !self.out_of_fuel
+                    {
                         let i = *line;
                         let next = if i < self.shape.len() - 1 { i + 1 } else { 0 };
 
@@ -426,19 +721,14 @@ impl SoftBody {
                             .perp()
                             .normalize_or_zero();
 
-                        let force = direction.rotate(*force);
+                        let force = direction.rotate(force);
+                        let direction = force.normalize_or_zero();
 
                         point_a.impulse += force / 2.0 * dt;
                         point_b.impulse += force / 2.0 * dt;
 
                         *particle_time += dt;
                         while particle_time > max_particle_time {
-                            let color = utils::color_lerp(
-                                colors::RED,
-                                colors::YELLOW,
-                                macroquad::rand::gen_range(0.0, 1.0),
-                            );
-
                             let velocity = -direction * macroquad::rand::gen_range(10.0, 30.0)
                                 + direction.perp() * macroquad::rand::gen_range(-2.0, 2.0)
                                 + (point_a.velocity + point_b.velocity) / 2.0;
@@ -462,8 +752,8 @@ impl SoftBody {
                                 end_age: 1.0,
                                 start_velocity: velocity,
                                 end_velocity: velocity,
-                                start_color: color,
-                                end_color: Color { a: 0.0, ..color },
+                                start_color: *exhaust_start,
+                                end_color: *exhaust_end,
                                 start_rotation: rotation,
                                 end_rotation: rotation + macroquad::rand::gen_range(-10.0, 10.0),
                                 start_size: macroquad::rand::gen_range(0.1, 0.2),
@@ -473,13 +763,16 @@ impl SoftBody {
                         }
                     }
                 }
-                Actor::HabitatBubble { minimum_pressure } => {
+                Actor::HabitatBubble {
+                    minimum_pressure, ..
+                } => {
                     if self.pressure > *minimum_pressure {
                         new_camera_position = Some(center_of_mass);
                     }
                 }
                 Actor::Piston { lengths, enable } => {
-                    let enabled = enable.is_down() && self.connection_state.is_connected();
+                    let enabled =
+                        enable.is_down(suppressed_keys) && self.connection_state.is_connected();
 
                     for (line, off_length, on_length) in &*lengths {
                         let spring = match line {
@@ -490,9 +783,69 @@ impl SoftBody {
                         spring.target_distance = if enabled { *on_length } else { *off_length };
                     }
                 }
+                Actor::ReactionWheel {
+                    torque,
+                    enable_cw,
+                    enable_ccw,
+                } => {
+                    if !self.connection_state.is_connected() {
+                        continue;
+                    }
+
+                    let direction = enable_ccw.is_down(suppressed_keys) as u8 as f32
+                        - enable_cw.is_down(suppressed_keys) as u8 as f32;
+
+                    total_torque += direction * *torque;
+                }
+                Actor::OrientationSpring {
+                    line,
+                    target_direction,
+                    stiffness,
+                    damping,
+                } => {
+                    let i = *line;
+                    let next = if i < self.shape.len() - 1 { i + 1 } else { 0 };
+                    let (a, b) = (self.shape[i].0.position, self.shape[next].0.position);
+
+                    let mut total_mass = 0.0;
+                    let mut momentum = Vec2::ZERO;
+                    let mut moment_of_inertia = 0.0;
+
+                    for (point, _) in &self.shape {
+                        total_mass += point.mass;
+                        momentum += point.velocity * point.mass;
+                        moment_of_inertia +=
+                            point.mass * point.position.distance_squared(center_of_mass);
+                    }
+
+                    if moment_of_inertia <= f32::EPSILON {
+                        continue;
+                    }
+
+                    let velocity = momentum / total_mass;
+
+                    let angular_velocity = self
+                        .shape
+                        .iter()
+                        .map(|(point, _)| {
+                            (point.position - center_of_mass).perp_dot(point.velocity - velocity)
+                                * point.mass
+                        })
+                        .sum::<f32>()
+                        / moment_of_inertia;
+
+                    let angle =
+                        utils::angle_difference((b - a).to_angle(), target_direction.to_angle());
+                    total_torque += *stiffness * angle - *damping * angular_velocity;
+                }
+                Actor::Label { .. } => (),
             }
         }
 
+        if total_torque != 0.0 {
+            self.apply_torque(total_torque, dt);
+        }
+
         (new_camera_position, new_particles)
     }
 
@@ -528,31 +881,64 @@ impl SoftBody {
             min_corner: min,
             size,
         };
+
+        self.cached_centroid = self.calculate_centroid();
+        self.cached_center_of_mass = self.calculate_center_of_mass();
+
+        self.bounding_radius = self
+            .shape
+            .iter()
+            .map(|(point, _)| point.position.distance_squared(self.cached_centroid))
+            .fold(0.0, f32::max)
+            .sqrt();
     }
 
+    /// The index of the point after `i`, wrapping around to `0` after the last point.
+    ///
+    /// Does not check that `i` is in range. Returns `0` if the shape is empty.
     pub fn next_point(&self, i: usize) -> usize {
-        if i < self.shape.len() - 1 { i + 1 } else { 0 }
+        if i + 1 < self.shape.len() { i + 1 } else { 0 }
     }
 
+    /// The index of the point before `i`, wrapping around to the last point before `0`.
+    ///
+    /// Does not check that `i` is in range. Returns `0` if the shape is empty.
     pub fn previous_point(&self, i: usize) -> usize {
-        if i > 0 { i - 1 } else { self.shape.len() - 1 }
+        if i > 0 {
+            i - 1
+        } else {
+            self.shape.len().saturating_sub(1)
+        }
     }
 
+    /// Returns the line starting at point `i` and ending at the next point.
+    ///
+    /// Returns `None` if `i` is out of range or the shape has fewer than 2 points.
     pub fn get_line(&self, i: usize) -> Option<(&Point, &Line, &Point)> {
+        if self.shape.len() < 2 {
+            return None;
+        }
+
         let (point_a, line) = self.shape.get(i)?;
-        let (point_b, _) = &self.shape[self.next_point(i)];
+        let (point_b, _) = self.shape.get(self.next_point(i))?;
 
         Some((point_a, line, point_b))
     }
 
+    /// Iterates over every line of the shape, in the same form as [`Self::get_line`].
+    pub fn edges(&self) -> impl Iterator<Item = (&Point, &Line, &Point)> {
+        (0..self.shape.len()).filter_map(|i| self.get_line(i))
+    }
+
+    /// See [`Self::get_line`]
     pub fn get_line_mut(&mut self, i: usize) -> Option<(&mut Point, &mut Line, &mut Point)> {
-        if i >= self.shape.len() {
+        if i >= self.shape.len() || self.shape.len() < 2 {
             return None;
         }
 
         let next = self.next_point(i);
 
-        let [(point_a, line), (point_b, _)] = self.shape.get_disjoint_mut([i, next]).unwrap();
+        let [(point_a, line), (point_b, _)] = self.shape.get_disjoint_mut([i, next]).ok()?;
 
         Some((point_a, line, point_b))
     }
@@ -567,16 +953,29 @@ impl SoftBody {
         Some(self.length_squared_of_line(i)?.sqrt())
     }
 
+    /// Returns the previous point, point `i`, and the next point, in that order.
+    ///
+    /// Returns `None` if `i` is out of range or the shape has fewer than 3 points.
     pub fn get_angle(&self, i: usize) -> Option<[&Point; 3]> {
+        if self.shape.len() < 3 {
+            return None;
+        }
+
         let (point_b, _) = self.shape.get(i)?;
-        let (point_c, _) = &self.shape[self.next_point(i)];
-        let (point_a, _) = &self.shape[self.previous_point(i)];
+        let (point_c, _) = self.shape.get(self.next_point(i))?;
+        let (point_a, _) = self.shape.get(self.previous_point(i))?;
 
         Some([point_a, point_b, point_c])
     }
 
+    /// Iterates over every angle of the shape, in the same form as [`Self::get_angle`].
+    pub fn angles(&self) -> impl Iterator<Item = [&Point; 3]> {
+        (0..self.shape.len()).filter_map(|i| self.get_angle(i))
+    }
+
+    /// See [`Self::get_angle`]
     pub fn get_angle_mut(&mut self, i: usize) -> Option<[&mut Point; 3]> {
-        if i >= self.shape.len() {
+        if i >= self.shape.len() || self.shape.len() < 3 {
             return None;
         }
 
@@ -584,14 +983,14 @@ impl SoftBody {
         let previous = self.previous_point(i);
 
         let [(point_a, _), (point_b, _), (point_c, _)] =
-            self.shape.get_disjoint_mut([previous, i, next]).unwrap();
+            self.shape.get_disjoint_mut([previous, i, next]).ok()?;
 
         Some([point_a, point_b, point_c])
     }
 
     pub fn get_adjacent_lines_to_point(&self, i: usize) -> Option<[&Line; 2]> {
         let (_, line_b) = self.shape.get(i)?;
-        let (_, line_a) = &self.shape[self.previous_point(i)];
+        let (_, line_a) = self.shape.get(self.previous_point(i))?;
 
         Some([line_a, line_b])
     }
@@ -605,14 +1004,35 @@ impl SoftBody {
         ))
     }
 
+    /// Whether any point of either shape is inside the other, or any of their edges cross, as
+    /// they do when two thin shapes lie across each other
+    pub fn overlaps(&self, other: &SoftBody) -> bool {
+        self.bounding_box.intersects_other(&other.bounding_box)
+            && (self
+                .shape
+                .iter()
+                .any(|(point, _)| other.contains_point(point.position))
+                || other
+                    .shape
+                    .iter()
+                    .any(|(point, _)| self.contains_point(point.position))
+                || self.edges().any(|(point_a, _, point_b)| {
+                    other.edges().any(|(point_c, _, point_d)| {
+                        utils::line_segment_intersection(
+                            [point_a.position, point_b.position],
+                            [point_c.position, point_d.position],
+                        )
+                        .is_some()
+                    })
+                }))
+    }
+
     pub fn contains_point(&self, point: Vec2) -> bool {
         if self.bounding_box.contains_point(point) {
             // Cast a horizontal line to the right of the point
             let mut num_intersections = 0;
 
-            for i in 0..self.shape.len() {
-                let (point_a, _, point_b) = self.get_line(i).unwrap();
-
+            for (point_a, _, point_b) in self.edges() {
                 let point_a = point_a.position;
                 let point_b = point_b.position;
 
@@ -696,8 +1116,96 @@ impl SoftBody {
         utils::closest_point_on_line(start.position, end.position, point)
     }
 
-    pub fn check_points_against_other_one_sided(&mut self, other: &mut SoftBody) -> bool {
-        let mut collided = false;
+    /// Checks the path each point took during the last tick against the edges of `other`, and
+    /// resolves points which passed all the way through an edge where they first crossed it.
+    /// Points inside `other` are left for [`Self::check_points_against_other_one_sided`], which
+    /// reports collision events and returns the largest impulse the same way.
+    pub fn check_swept_points_against_other(
+        &mut self,
+        other: &mut SoftBody,
+        collision_events: &mut Vec<CollisionEvent>,
+    ) -> f32 {
+        if self.previous_positions.len() != self.shape.len() || self.frozen && other.frozen {
+            return 0.0;
+        }
+
+        let frozen = self.frozen;
+        let mut largest_impulse = 0.0_f32;
+
+        for i in 0..self.shape.len() {
+            let previous_position = self.previous_positions[i];
+            let point_friction = self.get_friction_of_point(i).unwrap();
+            let point = &mut self.shape[i].0;
+
+            let path = [previous_position, point.position];
+
+            if !BoundingBox::fit_points(&path).intersects_other(&other.bounding_box)
+                || other.contains_point(point.position)
+            {
+                continue;
+            }
+
+            let mut first_crossing: Option<(f32, usize, Vec2, f32)> = None;
+
+            for (line, (point_a, _, point_b)) in other.edges().enumerate() {
+                let outwards = (point_a.position - point_b.position).perp();
+
+                // Only count points moving into `other`
+                if outwards.dot(point.position - previous_position) >= 0.0 {
+                    continue;
+                }
+
+                if let Some((position, [t, u])) = utils::intersection_point_of_line_segments(
+                    path,
+                    [point_a.position, point_b.position],
+                ) && first_crossing.is_none_or(|(first_t, ..)| t < first_t)
+                {
+                    first_crossing = Some((t, line, position, u));
+                }
+            }
+
+            let Some((_, line, position, interpolation)) = first_crossing else {
+                continue;
+            };
+
+            point.position = position;
+
+            let impulse = other.check_other_point_against_line(
+                point,
+                frozen,
+                point_friction,
+                line,
+                position,
+                interpolation,
+            );
+
+            if impulse >= Self::MINIMUM_COLLISION_EVENT_IMPULSE {
+                collision_events.push(CollisionEvent {
+                    position: point.position,
+                    impulse,
+                });
+            }
+
+            largest_impulse = largest_impulse.max(impulse);
+        }
+
+        largest_impulse
+    }
+
+    /// Collisions with an impulse of at least [`Self::MINIMUM_COLLISION_EVENT_IMPULSE`] are added
+    /// to `collision_events`. Returns the largest impulse of any point, or zero if none collided,
+    /// so that the caller can apply damage once for both directions with [`Self::take_damage`].
+    pub fn check_points_against_other_one_sided(
+        &mut self,
+        other: &mut SoftBody,
+        collision_events: &mut Vec<CollisionEvent>,
+    ) -> f32 {
+        if self.frozen && other.frozen {
+            return 0.0;
+        }
+
+        let frozen = self.frozen;
+        let mut largest_impulse = 0.0_f32;
 
         for i in 0..self.shape.len() {
             let point_friction = self.get_friction_of_point(i).unwrap();
@@ -710,8 +1218,9 @@ impl SoftBody {
             let (line, closest_point, _, interpolation) =
                 other.closest_line_to_point(point.position);
 
-            other.check_other_point_against_line(
+            let mut impulse = other.check_other_point_against_line(
                 point,
+                frozen,
                 point_friction,
                 line,
                 closest_point,
@@ -720,8 +1229,9 @@ impl SoftBody {
 
             if interpolation <= f32::EPSILON {
                 // Wedged into corner
-                other.check_other_point_against_line(
+                impulse += other.check_other_point_against_line(
                     point,
+                    frozen,
                     point_friction,
                     if line == 0 {
                         other.shape.len() - 1
@@ -733,8 +1243,9 @@ impl SoftBody {
                 )
             } else if interpolation >= 1.0 - f32::EPSILON {
                 // Wedged into corner
-                other.check_other_point_against_line(
+                impulse += other.check_other_point_against_line(
                     point,
+                    frozen,
                     point_friction,
                     if line >= other.shape.len() - 1 {
                         0
@@ -746,10 +1257,26 @@ impl SoftBody {
                 )
             }
 
-            collided = true;
+            if impulse >= Self::MINIMUM_COLLISION_EVENT_IMPULSE {
+                collision_events.push(CollisionEvent {
+                    position: point.position,
+                    impulse,
+                });
+            }
+
+            largest_impulse = largest_impulse.max(impulse);
         }
 
-        collided
+        largest_impulse
+    }
+
+    /// Takes away as much health as `impulse` exceeds [`Self::MINIMUM_DAMAGE_IMPULSE`] by
+    pub fn take_damage(&mut self, impulse: f32) {
+        let damage = impulse - Self::MINIMUM_DAMAGE_IMPULSE;
+
+        if damage > 0.0 {
+            self.health -= damage;
+        }
     }
 
     // pub fn resolve_all_self_intersections(&mut self) {
@@ -811,24 +1338,28 @@ impl SoftBody {
     //     self.check_own_point_against_line(point, line, intersection_point, interpolation);
     // }
 
+    /// `point_frozen` is whether the soft body `point` is part of is [`Self::frozen`]
     pub fn check_other_point_against_line(
         &mut self,
         point: &mut Point,
+        point_frozen: bool,
         point_friction: f32,
         line: usize,
         closest_point: Vec2,
         interpolation: f32,
-    ) {
+    ) -> f32 {
+        let frozen = [point_frozen, self.frozen];
         let (point_a, Line { friction, .. }, point_b) = self.get_line_mut(line).unwrap();
 
         Self::check_point_against_line(
             point_a,
             point_b,
             point,
+            frozen,
             utils::combine_friction(point_friction, *friction),
             closest_point,
             interpolation,
-        );
+        )
     }
 
     pub fn check_own_point_against_line(
@@ -837,15 +1368,16 @@ impl SoftBody {
         line: usize,
         closest_point: Vec2,
         interpolation: f32,
-    ) {
+    ) -> f32 {
         let point_friction = self.get_friction_of_point(point).unwrap();
+        let frozen = [self.frozen; 2];
 
         let next = self.next_point(line);
 
         let [(point, _), (point_a, Line { friction, .. }), (point_b, _)] =
             match self.shape.get_disjoint_mut([point, line, next]) {
                 Ok(value) => value,
-                Err(GetDisjointMutError::OverlappingIndices) => return,
+                Err(GetDisjointMutError::OverlappingIndices) => return 0.0,
                 Err(GetDisjointMutError::IndexOutOfBounds) => panic!("Index out of bounds"),
             };
 
@@ -853,20 +1385,29 @@ impl SoftBody {
             point_a,
             point_b,
             point,
+            frozen,
             utils::combine_friction(point_friction, *friction),
             closest_point,
             interpolation,
-        );
+        )
     }
 
+    /// Returns the magnitude of the impulse applied to `point` along the normal of the line.
+    /// `frozen` is whether the soft bodies of `point` and of the line are [`Self::frozen`], which
+    /// makes the collision treat them as if they had infinite mass.
     pub fn check_point_against_line(
         point_a: &mut Point,
         point_b: &mut Point,
         point: &mut Point,
+        [point_frozen, line_frozen]: [bool; 2],
         friction: f32,
         closest_point: Vec2,
         interpolation: f32,
-    ) {
+    ) -> f32 {
+        if point_frozen && line_frozen {
+            return 0.0;
+        }
+
         // Will move the points just the right distance so the line intersects the new position
         let interpolation_scale = utils::interpolation_scale(interpolation);
 
@@ -874,9 +1415,16 @@ impl SoftBody {
         let composite_mass =
             utils::lerp(point_a.mass, point_b.mass, interpolation) * interpolation_scale;
 
-        point.position = point
-            .position
-            .lerp(closest_point, point.mass / (point.mass + composite_mass));
+        // How much of the position and velocity correction is given to `point` rather than the line
+        let (point_share, point_friction_share) = if line_frozen {
+            (1.0, 1.0)
+        } else if point_frozen {
+            (0.0, 0.0)
+        } else {
+            (point.mass / (point.mass + composite_mass), 0.5)
+        };
+
+        point.position = point.position.lerp(closest_point, point_share);
 
         let composite_position_nudge = point.position - closest_point;
 
@@ -908,20 +1456,29 @@ impl SoftBody {
             }
         };
 
-        let weighted_normal_velocity = (point_normal_velocity * point.mass
-            + composite_normal_velocity * composite_mass)
-            / (point.mass + composite_mass);
+        let weighted_normal_velocity = if line_frozen {
+            composite_normal_velocity
+        } else if point_frozen {
+            point_normal_velocity
+        } else {
+            (point_normal_velocity * point.mass + composite_normal_velocity * composite_mass)
+                / (point.mass + composite_mass)
+        };
+
+        let normal_velocity_nudge = weighted_normal_velocity - point_normal_velocity;
 
-        point.velocity +=
-            weighted_normal_velocity - point_normal_velocity + friction_velocity_nudge / 2.0;
-        let composite_velocity_nudge =
-            weighted_normal_velocity - composite_normal_velocity - friction_velocity_nudge / 2.0;
+        point.velocity += normal_velocity_nudge + friction_velocity_nudge * point_friction_share;
+        let composite_velocity_nudge = weighted_normal_velocity
+            - composite_normal_velocity
+            - friction_velocity_nudge * (1.0 - point_friction_share);
 
         point_a.velocity += composite_velocity_nudge * (1.0 - interpolation) * interpolation_scale;
         point_b.velocity += composite_velocity_nudge * interpolation * interpolation_scale;
 
         point_a.position += composite_position_nudge * (1.0 - interpolation) * interpolation_scale;
         point_b.position += composite_position_nudge * interpolation * interpolation_scale;
+
+        normal_velocity_nudge.length() * point.mass
     }
 
     /// CREDIT: chmike: <https://stackoverflow.com/a/717367>
@@ -945,6 +1502,78 @@ impl SoftBody {
     }
 
     pub fn is_self_intersecting(&self) -> bool {
+        self.self_intersection().is_some()
+    }
+
+    /// The area covered by the soft body both now and at [`Self::previous_positions`]
+    pub fn swept_area(&self) -> BoundingBox {
+        if self.previous_positions.is_empty() {
+            return self.bounding_box;
+        }
+
+        self.bounding_box
+            .union(&BoundingBox::fit_points(&self.previous_positions))
+    }
+
+    /// The furthest any point has moved since [`Self::previous_positions`] was recorded
+    pub fn max_displacement(&self) -> f32 {
+        self.shape
+            .iter()
+            .zip(&self.previous_positions)
+            .map(|((point, _), &previous_position)| point.position.distance(previous_position))
+            .fold(0.0, f32::max)
+    }
+
+    /// Moves every point back to [`Self::previous_positions`], returning how far each one was
+    /// moved back. Returns `None` if the number of points changed since they were recorded.
+    pub fn rewind(&mut self) -> Option<Vec<Vec2>> {
+        if self.previous_positions.len() != self.shape.len() {
+            return None;
+        }
+
+        let displacements = self
+            .shape
+            .iter_mut()
+            .zip(&self.previous_positions)
+            .map(|((point, _), &previous_position)| {
+                let displacement = point.position - previous_position;
+                point.position = previous_position;
+
+                displacement
+            })
+            .collect();
+
+        self.update_bounding_box();
+
+        Some(displacements)
+    }
+
+    /// Pushes the points of both edges outwards along their normals, to try to undo a fold found
+    /// by [`Self::self_intersection`]
+    pub fn push_fold_apart(&mut self, edges: [usize; 2], dt: f32) {
+        for edge in edges {
+            let Some((point_a, _, point_b)) = self.get_line_mut(edge) else {
+                continue;
+            };
+
+            let normal = (point_a.position - point_b.position)
+                .perp()
+                .normalize_or_zero();
+
+            for point in [point_a, point_b] {
+                point.impulse += normal * Self::FOLD_SEPARATION_ACCELERATION * point.mass * dt;
+            }
+        }
+    }
+
+    /// Where the shape first crosses over itself, if it does
+    pub fn self_intersection_point(&self) -> Option<Vec2> {
+        self.self_intersection().map(|(point, _)| point)
+    }
+
+    /// Returns the point where the shape crosses over itself, and the indecies of the two edges
+    /// which cross
+    pub fn self_intersection(&self) -> Option<(Vec2, [usize; 2])> {
         for i in 2..self.shape.len() {
             let start = if i == self.shape.len() - 1 { 1 } else { 0 };
 
@@ -952,25 +1581,67 @@ impl SoftBody {
                 let (a1, _, b1) = self.get_line(i).unwrap();
                 let (a2, _, b2) = self.get_line(j).unwrap();
 
-                if utils::are_line_segments_intersecting(
+                if let Some((point, _)) = utils::intersection_point_of_line_segments(
                     [a1.position, b1.position],
                     [a2.position, b2.position],
                 ) {
-                    return true;
+                    return Some((point, [i, j]));
                 }
             }
         }
 
-        false
+        None
     }
 
-    /// CREDIT: tirithen <https://github.com/not-fl3/macroquad/issues/174#issuecomment-817203498>
-    /// (made to work with convex polygons via earcut)
+    /// Only triangulates the shape again if its number of points has changed or one of its
+    /// triangles has been turned inside out, since the topology otherwise stays the same as the
+    /// points move.
+    ///
+    /// As long as none of the triangles are inverted, they still exactly cover the shape, even
+    /// if it has become concave.
     pub fn update_triangulation_indecies(&mut self) {
+        if self.triangulated_points != Some(self.shape.len()) || self.has_inverted_triangles() {
+            self.triangulate();
+        }
+    }
+
+    /// Returns `true` if any triangle in `triangle_indices` is noticeably oriented clockwise.
+    /// Triangles which are only slightly inverted are ignored so that points which are collinear
+    /// at rest don't cause the shape to be triangulated again every tick.
+    pub fn has_inverted_triangles(&self) -> bool {
+        const TOLERANCE: f32 = 0.05;
+
+        self.triangle_indices.chunks_exact(3).any(|chunk| {
+            let [a, b, c] =
+                [chunk[0], chunk[1], chunk[2]].map(|index| self.shape[index as usize].0.position);
+
+            let longest_side_squared = a
+                .distance_squared(b)
+                .max(b.distance_squared(c))
+                .max(c.distance_squared(a));
+
+            (b - a).perp_dot(c - a) < -TOLERANCE * longest_side_squared
+        })
+    }
+
+    /// CREDIT: tirithen <https://github.com/not-fl3/macroquad/issues/174#issuecomment-817203498>
+    /// (made to work with concave polygons via earcut)
+    pub fn triangulate(&mut self) {
         static EARCUT: LazyLock<Mutex<Earcut<f32>>> = LazyLock::new(|| Mutex::new(Earcut::new()));
 
+        self.triangulated_points = Some(self.shape.len());
         self.triangle_indices.clear();
-        EARCUT.lock().unwrap().earcut(
+
+        if self.is_convex() {
+            for i in 1..self.shape.len().saturating_sub(1) {
+                self.triangle_indices
+                    .extend_from_slice(&[0, i as u16, i as u16 + 1]);
+            }
+
+            return;
+        }
+
+        EARCUT.lock().unwrap().earcut(
             self.shape.iter().map(|(point, _)| point.position.into()),
             &[],
             &mut self.triangle_indices,
@@ -1013,11 +1684,61 @@ impl SoftBody {
             .collect()
     }
 
+    /// Returns `true` if no corner of the shape turns clockwise
+    pub fn is_convex(&self) -> bool {
+        self.angles().all(|[point_a, point_b, point_c]| {
+            (point_b.position - point_a.position).perp_dot(point_c.position - point_b.position)
+                >= 0.0
+        })
+    }
+
     pub fn is_debris(&self) -> bool {
         self.debris_age.is_some()
     }
 
+    /// The geometric center of the area enclosed by the shape, ignoring the mass of its points.
+    /// Use this for anything visual, such as where to draw something in the middle of the body.
+    ///
+    /// Cached when the bounding box is updated, so it may lag slightly behind the points.
+    pub fn centroid(&self) -> Vec2 {
+        self.cached_centroid
+    }
+
+    /// The average position of the points weighted by their mass. Use this for anything physical,
+    /// such as where to apply forces or which point the body rotates around.
+    ///
+    /// Cached when the bounding box is updated, so it may lag slightly behind the points.
     pub fn center_of_mass(&self) -> Vec2 {
+        self.cached_center_of_mass
+    }
+
+    /// CREDIT: Wikipedia: <https://en.wikipedia.org/wiki/Centroid#Of_a_polygon>
+    ///
+    /// Falls back to the average of the points if the shape has no area.
+    pub fn calculate_centroid(&self) -> Vec2 {
+        let mut double_area = 0.0;
+        let mut moment = Vec2::ZERO;
+
+        for (point_a, _, point_b) in self.edges() {
+            let cross = point_a.position.perp_dot(point_b.position);
+
+            double_area += cross;
+            moment += (point_a.position + point_b.position) * cross;
+        }
+
+        if double_area.abs() <= f32::EPSILON {
+            let total = self
+                .shape
+                .iter()
+                .fold(Vec2::ZERO, |total, (point, _)| total + point.position);
+
+            return total / self.shape.len().max(1) as f32;
+        }
+
+        moment / (3.0 * double_area)
+    }
+
+    pub fn calculate_center_of_mass(&self) -> Vec2 {
         let mut mass_moment = Vec2::ZERO;
         let mut total_mass = 0.0;
         for (point, _) in &self.shape {
@@ -1027,9 +1748,404 @@ impl SoftBody {
         mass_moment / total_mass
     }
 
+    /// Moves every point by `offset` and stops the body
+    pub fn translate(&mut self, offset: Vec2) {
+        for (point, _) in &mut self.shape {
+            point.position += offset;
+            point.velocity = Vec2::ZERO;
+        }
+
+        self.update_bounding_box();
+    }
+
+    /// Rotates every point counter clockwise by `angle` radians around `center` and stops the body
+    pub fn rotate_around(&mut self, center: Vec2, angle: f32) {
+        let rotation = Vec2::from_angle(angle);
+
+        for (point, _) in &mut self.shape {
+            point.position = center + rotation.rotate(point.position - center);
+            point.velocity = Vec2::ZERO;
+        }
+
+        self.update_bounding_box();
+    }
+
+    /// Adds `actor` after the others and returns its index, or returns `None` without adding it if
+    /// it refers to an edge or spring this doesn't have
+    pub fn add_actor(&mut self, actor: Actor) -> Option<usize> {
+        if !actor.is_valid(self.shape.len(), self.internal_springs.len()) {
+            return None;
+        }
+
+        self.actors.push(actor);
+
+        Some(self.actors.len() - 1)
+    }
+
+    /// Removes and returns the actor at `index`, or `None` if there isn't one
+    pub fn remove_actor(&mut self, index: usize) -> Option<Actor> {
+        (index < self.actors.len()).then(|| self.actors.remove(index))
+    }
+
+    /// Switches a rocket motor or piston on or off regardless of the keys held, until
+    /// [`Self::clear_actor_override`] is called. Does nothing for other actors.
+    pub fn set_actor_enabled(&mut self, actor_index: usize, enabled: bool) {
+        if let Some(enable) = self.actors.get_mut(actor_index).and_then(Actor::enable_mut) {
+            enable.forced = Some(enabled);
+        }
+    }
+
+    /// Gives control of a rocket motor or piston back to its keys. See
+    /// [`Self::set_actor_enabled`]
+    pub fn clear_actor_override(&mut self, actor_index: usize) {
+        if let Some(enable) = self.actors.get_mut(actor_index).and_then(Actor::enable_mut) {
+            enable.forced = None;
+        }
+    }
+
+    /// Whether a rocket motor or piston is switched on, by its keys or by
+    /// [`Self::set_actor_enabled`]. Returns `None` for other actors or if the index is out of
+    /// range.
+    pub fn is_actor_enabled(
+        &self,
+        actor_index: usize,
+        suppressed_keys: &[KeyCode],
+    ) -> Option<bool> {
+        Some(
+            self.actors
+                .get(actor_index)?
+                .enable()?
+                .is_down(suppressed_keys),
+        )
+    }
+
+    /// A copy with at most `target_points` points, but never fewer than 3, made by merging the ends
+    /// of the shortest edge into one point at their center of mass until few enough are left. The
+    /// total mass and momentum are kept, and the springs rest at the simplified shape so it keeps
+    /// roughly the same area. Meant for standing in for distant soft bodies, so the internal
+    /// springs, attatchment points, actors, and constraints are left out.
+    pub fn simplified(&self, target_points: usize) -> SoftBody {
+        let mut shape = self
+            .shape
+            .iter()
+            .map(|&(point, line)| {
+                (
+                    Point {
+                        constraint: None,
+                        num_connections: 0,
+                        ..point
+                    },
+                    line,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        while shape.len() > target_points.max(3) {
+            let len = shape.len();
+            let edge_length =
+                |i: usize| (shape[i].0.position).distance_squared(shape[(i + 1) % len].0.position);
+
+            let i = (0..len)
+                .min_by(|&a, &b| edge_length(a).total_cmp(&edge_length(b)))
+                .unwrap();
+            let next = (i + 1) % len;
+
+            let (point_b, line_b) = shape[next];
+            let (point_a, line_a) = &mut shape[i];
+
+            let mass = point_a.mass + point_b.mass;
+
+            point_a.position =
+                (point_a.position * point_a.mass + point_b.position * point_b.mass) / mass;
+            point_a.velocity =
+                (point_a.velocity * point_a.mass + point_b.velocity * point_b.mass) / mass;
+            point_a.impulse += point_b.impulse;
+            point_a.mass = mass;
+            *line_a = line_b;
+
+            shape.remove(next);
+        }
+
+        let mut soft_body = SoftBody::new(shape, Vec::new(), self.gas_force);
+
+        for i in 0..soft_body.shape.len() {
+            let (point_a, line, point_b) = soft_body.get_line_mut(i).unwrap();
+
+            line.spring.target_distance = point_a.position.distance(point_b.position);
+
+            let [point_a, point_b, point_c] = soft_body.get_angle_mut(i).unwrap();
+
+            if let Some(spring) = &mut point_b.spring {
+                spring.target_angle = (point_b.position - point_a.position)
+                    .angle_between(point_c.position - point_b.position);
+            }
+        }
+
+        soft_body.debris_age = self.debris_age;
+        soft_body.collision_layer = self.collision_layer;
+        soft_body.collision_mask = self.collision_mask;
+        soft_body.health = self.health;
+        soft_body.frozen = self.frozen;
+        soft_body.id = self.id;
+
+        soft_body
+    }
+
+    /// The indecies of the points of an attatchment point in order, wrapping around past the last
+    /// point. Empty if the index is out of range.
+    pub fn attatchment_point_indecies(&self, index: usize) -> impl Iterator<Item = usize> + use<> {
+        let num_points = self.shape.len();
+        let (start_point, length) = self
+            .attatchment_points
+            .get(index)
+            .map_or((0, 0), |attatchment_point| {
+                (attatchment_point.start_point, attatchment_point.length)
+            });
+
+        (0..length).map(move |i| (start_point + i) % num_points)
+    }
+
+    /// The positions of the points of an attatchment point in order. See
+    /// [`Self::attatchment_point_indecies`]
+    pub fn attatchment_point_positions(&self, index: usize) -> impl Iterator<Item = Vec2> {
+        self.attatchment_point_indecies(index)
+            .map(|i| self.shape[i].0.position)
+    }
+
+    /// The average position of the points of an attatchment point. Returns `None` if the index is
+    /// out of range.
+    pub fn attatchment_point_midpoint(&self, index: usize) -> Option<Vec2> {
+        let length = self.attatchment_points.get(index)?.length;
+
+        Some(self.attatchment_point_positions(index).sum::<Vec2>() / length as f32)
+    }
+
+    /// The direction from the first point of an attatchment point to its last point. Returns
+    /// `None` if the index is out of range or it has fewer than 2 points.
+    pub fn attatchment_point_direction(&self, index: usize) -> Option<Vec2> {
+        let attatchment_point = self.attatchment_points.get(index)?;
+
+        if attatchment_point.length < 2 {
+            return None;
+        }
+
+        let first = attatchment_point.start_point;
+        let last = (first + attatchment_point.length - 1) % self.shape.len();
+
+        Some(self.shape.get(last)?.0.position - self.shape.get(first)?.0.position)
+    }
+
+    pub fn total_mass(&self) -> f32 {
+        self.shape.iter().map(|(point, _)| point.mass).sum()
+    }
+
+    /// The velocity of the center of mass
+    pub fn velocity(&self) -> Vec2 {
+        let momentum = self.shape.iter().fold(Vec2::ZERO, |momentum, (point, _)| {
+            momentum + point.momentum()
+        });
+
+        momentum / self.total_mass()
+    }
+
+    /// Counter clockwise angular momentum about the center of mass
+    pub fn angular_momentum(&self) -> f32 {
+        let center_of_mass = self.calculate_center_of_mass();
+        let velocity = self.velocity();
+
+        self.shape
+            .iter()
+            .map(|(point, _)| {
+                (point.position - center_of_mass).perp_dot(point.velocity - velocity) * point.mass
+            })
+            .sum()
+    }
+
+    /// About the center of mass
+    pub fn moment_of_inertia(&self) -> f32 {
+        let center_of_mass = self.calculate_center_of_mass();
+
+        self.shape
+            .iter()
+            .map(|(point, _)| point.position.distance_squared(center_of_mass) * point.mass)
+            .sum()
+    }
+
+    /// Counter clockwise angular velocity about the center of mass in radians per second
+    pub fn angular_velocity(&self) -> f32 {
+        let moment_of_inertia = self.moment_of_inertia();
+
+        if moment_of_inertia <= f32::EPSILON {
+            return 0.0;
+        }
+
+        self.angular_momentum() / moment_of_inertia
+    }
+
+    /// Slows the spinning of the soft body about its center of mass by `damping` of its angular
+    /// velocity per second, without pushing it anywhere
+    pub fn damp_angular_velocity(&mut self, damping: f32, dt: f32) {
+        if damping <= 0.0 || dt <= 0.0 {
+            return;
+        }
+
+        let change = -self.angular_velocity() * (damping * dt).min(1.0);
+
+        self.apply_torque(change * self.moment_of_inertia() / dt, dt);
+    }
+
+    /// Spins the soft body about its center of mass for `dt` seconds without pushing it anywhere.
+    /// Each point's impulse is weighted by its mass around the center of mass, so they cancel out.
+    pub fn apply_torque(&mut self, torque: f32, dt: f32) {
+        let center_of_mass = self.calculate_center_of_mass();
+        let moment_of_inertia = self.moment_of_inertia();
+
+        if moment_of_inertia <= f32::EPSILON {
+            return;
+        }
+
+        let angular_acceleration = torque / moment_of_inertia;
+
+        for (point, _) in &mut self.shape {
+            point.impulse +=
+                (point.position - center_of_mass).perp() * point.mass * angular_acceleration * dt;
+        }
+    }
+
+    /// Draws an arc around the center of mass which is as long as the distance the body would
+    /// rotate in one second, up to a full turn, with the angular velocity in degrees per second
+    /// written below it.
+    pub fn draw_angular_velocity(&self) {
+        let center_of_mass = self.center_of_mass();
+        let angular_velocity = self.angular_velocity();
+        let color = utils::generate_color_for_spring(angular_velocity, 0.0);
+
+        let arc = angular_velocity.clamp(-TAU, TAU);
+
+        shapes::draw_arc(
+            center_of_mass.x,
+            center_of_mass.y,
+            64,
+            0.5,
+            arc.min(0.0).to_degrees(),
+            0.05,
+            arc.abs().to_degrees(),
+            color,
+        );
+
+        utils::draw_world_text(
+            &format!("{:.1} deg/s", angular_velocity.to_degrees()),
+            center_of_mass - vec2(0.0, 0.8),
+            Self::HEALTH_TEXT_SIZE,
+            color,
+        );
+    }
+
     pub fn uses_keybinds(&self) -> bool {
         self.actors.iter().any(Actor::uses_keybinds)
     }
+
+    /// Whether this soft body's rocket motors can fire
+    pub fn thrust_available(&self) -> bool {
+        self.connection_state.is_connected() && !self.out_of_fuel
+    }
+
+    /// Every spring along an edge, followed by every internal spring
+    pub fn linear_springs_mut(&mut self) -> impl Iterator<Item = &mut LinearSpring> {
+        self.shape
+            .iter_mut()
+            .map(|(_, line)| &mut line.spring)
+            .chain(self.internal_springs.iter_mut().map(|(_, spring)| spring))
+    }
+
+    pub fn angular_springs_mut(&mut self) -> impl Iterator<Item = &mut AngularSpring> {
+        self.shape
+            .iter_mut()
+            .filter_map(|(point, _)| point.spring.as_mut())
+    }
+
+    pub fn snapshot(&self) -> SoftBodySnapshot {
+        SoftBodySnapshot {
+            points: self
+                .shape
+                .iter()
+                .map(|(point, _)| (point.position, point.velocity))
+                .collect(),
+            pressure: self.pressure,
+            debris_age: self.debris_age,
+        }
+    }
+
+    /// Returns `None` without changing anything if the number of points has changed since the
+    /// snapshot was taken.
+    #[must_use]
+    pub fn restore(&mut self, snapshot: &SoftBodySnapshot) -> Option<()> {
+        if snapshot.points.len() != self.shape.len() {
+            return None;
+        }
+
+        for ((point, _), &(position, velocity)) in self.shape.iter_mut().zip(&snapshot.points) {
+            point.position = position;
+            point.velocity = velocity;
+            point.impulse = Vec2::ZERO;
+        }
+
+        self.pressure = snapshot.pressure;
+        self.debris_age = snapshot.debris_age;
+
+        self.update_bounding_box();
+
+        Some(())
+    }
+}
+
+/// The state of a [`SoftBody`] that changes while it is simulated. Can be written back with
+/// [`SoftBody::restore`].
+#[derive(Clone, Debug)]
+pub struct SoftBodySnapshot {
+    /// `(position, velocity)` of each point
+    pub points: Vec<(Vec2, Vec2)>,
+    pub pressure: f32,
+    pub debris_age: Option<f32>,
+}
+
+/// Moves every point without changing its velocity, including the positions from before the last
+/// tick so the move isn't treated as movement. The cached bounds are moved along with them instead
+/// of being recalculated.
+impl AddOffset for SoftBody {
+    fn add_offset(&mut self, offset: Vector2<f32>) {
+        let offset = vec2(offset.x, offset.y);
+
+        for (point, _) in &mut self.shape {
+            point.position += offset;
+        }
+
+        for position in &mut self.previous_positions {
+            *position += offset;
+        }
+
+        self.bounding_box.min_corner += offset;
+        self.cached_centroid += offset;
+        self.cached_center_of_mass += offset;
+    }
+}
+
+/// How points move each tick, given the impulse applied to them. The impulse is always found from
+/// the positions at the start of the tick.
+///
+/// Only [`Self::SemiImplicitEuler`] is symplectic, so the energy of an undamped spring oscillates
+/// around the right value instead of drifting. [`Self::Leapfrog`] doesn't find the impulse again
+/// halfway through the tick like velocity Verlet would, so the energy of an undamped spring grows,
+/// faster the stiffer the spring is. The damping of most springs hides this.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Integrator {
+    /// Moves the point by half of its old velocity and half of its new velocity. The position
+    /// follows the impulse more closely, but the energy drifts upwards.
+    #[default]
+    Leapfrog,
+    /// Moves the point by its new velocity. Only first order accurate, but the energy doesn't
+    /// drift, and the position reacts to an impulse on the same tick it is applied.
+    SemiImplicitEuler,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1037,6 +2153,7 @@ pub struct Point {
     pub position: Vec2,
     pub velocity: Vec2,
     pub impulse: Vec2,
+    /// Must be positive, since impulses are divided by it. See [`Self::MINIMUM_MASS`]
     pub mass: f32,
     pub spring: Option<AngularSpring>,
     pub constraint: Option<ConstraintKey>,
@@ -1044,13 +2161,28 @@ pub struct Point {
 }
 
 impl Point {
-    pub fn apply_impulse_and_velocity(&mut self, dt: f32) {
-        self.position += self.velocity / 2.0 * dt;
+    /// The smallest mass [`SoftBodyBuilder`] will give a point
+    pub const MINIMUM_MASS: f32 = 1e-3;
+
+    pub fn apply_impulse_and_velocity(&mut self, dt: f32, integrator: Integrator) {
+        debug_assert!(self.mass > 0.0, "Point has a mass of {}", self.mass);
+
+        match integrator {
+            Integrator::Leapfrog => {
+                self.position += self.velocity / 2.0 * dt;
+
+                self.velocity += self.impulse / self.mass;
+                self.impulse = Vec2::ZERO;
 
-        self.velocity += self.impulse / self.mass;
-        self.impulse = Vec2::ZERO;
+                self.position += self.velocity / 2.0 * dt;
+            }
+            Integrator::SemiImplicitEuler => {
+                self.velocity += self.impulse / self.mass;
+                self.impulse = Vec2::ZERO;
 
-        self.position += self.velocity / 2.0 * dt;
+                self.position += self.velocity * dt;
+            }
+        }
     }
 
     pub fn momentum(&self) -> Vec2 {
@@ -1087,11 +2219,16 @@ impl Default for Line {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct AttatchmentPoint {
     pub start_point: usize,
     pub length: usize,
-    pub connection: Option<AttatchmentPointHandle>,
+    /// The attatchment points this one is connected to. Usually there is only one, but dragging a
+    /// free seam onto one which is already connected joins it to the same junction, so up to
+    /// [`Simulation::MAX_SEAM_CONNECTIONS`] seams can be held together at one place.
+    ///
+    /// [`Simulation::MAX_SEAM_CONNECTIONS`]: crate::simulation::Simulation::MAX_SEAM_CONNECTIONS
+    pub connections: Vec<AttatchmentPointHandle>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -1108,14 +2245,92 @@ pub enum Actor {
         enable: Keybind,
         particle_time: f32,
         max_particle_time: f32,
+        /// Lets the thrust be steered away from `force`
+        gimbal: Option<Gimbal>,
+        /// The color of the exhaust particles when they are emitted
+        exhaust_start: Color,
+        /// The color the exhaust particles fade to before disappearing
+        exhaust_end: Color,
     },
     HabitatBubble {
         minimum_pressure: f32,
+        /// Shared by the rocket motors of every soft body connected to this one
+        fuel: f32,
+        fuel_capacity: f32,
     },
     Piston {
         lengths: Vec<(SpringIndex, f32, f32)>,
         enable: Keybind,
     },
+    /// Spins the soft body around its center of mass without pushing it anywhere
+    ReactionWheel {
+        torque: f32,
+        enable_cw: Keybind,
+        enable_ccw: Keybind,
+    },
+    /// Turns the soft body around its center of mass so the edge starting at `line` points along
+    /// `target_direction`, without pushing it anywhere. Passive, so it works without being
+    /// connected to a habitat bubble.
+    OrientationSpring {
+        line: usize,
+        /// Normalized
+        target_direction: Vec2,
+        /// Torque per radian the edge is turned away from `target_direction`
+        stiffness: f32,
+        /// Torque per radian per second the soft body is spinning
+        damping: f32,
+    },
+    /// Text drawn in the world, centered on the soft body's center of mass plus `offset`
+    Label {
+        offset: Vec2,
+        text: String,
+        /// The height of the text in world units
+        size: f32,
+    },
+}
+
+/// Rotates the thrust of a [`Actor::RocketMotor`] towards `max_angle` in either direction while
+/// its keybinds are held, and back to straight when neither is
+#[derive(Clone, Debug)]
+pub struct Gimbal {
+    /// In radians
+    pub max_angle: f32,
+    /// In radians, counterclockwise from `force`
+    pub angle: f32,
+    pub steer_left: Keybind,
+    pub steer_right: Keybind,
+}
+
+impl Gimbal {
+    /// How fast the thrust turns, in radians per second
+    pub const SPEED: f32 = 2.0;
+
+    pub fn update(&mut self, dt: f32, suppressed_keys: &[KeyCode]) {
+        let target = self.max_angle
+            * (self.steer_left.is_down(suppressed_keys) as u8 as f32
+                - self.steer_right.is_down(suppressed_keys) as u8 as f32);
+
+        self.angle += (target - self.angle).clamp(-Self::SPEED * dt, Self::SPEED * dt);
+    }
+}
+
+impl Default for Gimbal {
+    fn default() -> Self {
+        Self {
+            max_angle: 15f32.to_radians(),
+            angle: 0.0,
+            steer_left: Keybind {
+                activate: vec![KeyCode::A],
+                disable: vec![],
+                forced: None,
+            },
+            steer_right: Keybind {
+                activate: vec![KeyCode::D],
+                disable: vec![],
+                forced: None,
+            },
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -1125,10 +2340,160 @@ pub enum SpringIndex {
 }
 
 impl Actor {
+    pub const EXHAUST_START_COLOR: Color = colors::YELLOW;
+    pub const EXHAUST_END_COLOR: Color = Color {
+        a: 0.0,
+        ..colors::RED
+    };
+
     pub fn uses_keybinds(&self) -> bool {
         match self {
-            Actor::RocketMotor { .. } | Actor::Piston { .. } => true,
-            Actor::HabitatBubble { .. } => false,
+            Actor::RocketMotor { .. } | Actor::Piston { .. } | Actor::ReactionWheel { .. } => true,
+            Actor::HabitatBubble { .. } | Actor::OrientationSpring { .. } | Actor::Label { .. } => {
+                false
+            }
+        }
+    }
+
+    /// Every keybind of the actor, in the order they are shown in the editor
+    pub fn keybinds(&self) -> Vec<&Keybind> {
+        match self {
+            Actor::RocketMotor { enable, gimbal, .. } => {
+                let mut keybinds = vec![enable];
+
+                if let Some(gimbal) = gimbal {
+                    keybinds.extend([&gimbal.steer_left, &gimbal.steer_right]);
+                }
+
+                keybinds
+            }
+            Actor::Piston { enable, .. } => vec![enable],
+            Actor::ReactionWheel {
+                enable_cw,
+                enable_ccw,
+                ..
+            } => vec![enable_cw, enable_ccw],
+            Actor::HabitatBubble { .. } | Actor::OrientationSpring { .. } | Actor::Label { .. } => {
+                Vec::new()
+            }
+        }
+    }
+
+    /// See [`Self::keybinds`]
+    pub fn keybinds_mut(&mut self) -> Vec<&mut Keybind> {
+        match self {
+            Actor::RocketMotor { enable, gimbal, .. } => {
+                let mut keybinds = vec![enable];
+
+                if let Some(gimbal) = gimbal {
+                    keybinds.extend([&mut gimbal.steer_left, &mut gimbal.steer_right]);
+                }
+
+                keybinds
+            }
+            Actor::Piston { enable, .. } => vec![enable],
+            Actor::ReactionWheel {
+                enable_cw,
+                enable_ccw,
+                ..
+            } => vec![enable_cw, enable_ccw],
+            Actor::HabitatBubble { .. } | Actor::OrientationSpring { .. } | Actor::Label { .. } => {
+                Vec::new()
+            }
+        }
+    }
+
+    /// The keybind which switches a rocket motor or piston on, or `None` for other actors
+    pub fn enable(&self) -> Option<&Keybind> {
+        match self {
+            Actor::RocketMotor { enable, .. } | Actor::Piston { enable, .. } => Some(enable),
+            Actor::HabitatBubble { .. }
+            | Actor::ReactionWheel { .. }
+            | Actor::OrientationSpring { .. }
+            | Actor::Label { .. } => None,
+        }
+    }
+
+    /// See [`Self::enable`]
+    pub fn enable_mut(&mut self) -> Option<&mut Keybind> {
+        match self {
+            Actor::RocketMotor { enable, .. } | Actor::Piston { enable, .. } => Some(enable),
+            Actor::HabitatBubble { .. }
+            | Actor::ReactionWheel { .. }
+            | Actor::OrientationSpring { .. }
+            | Actor::Label { .. } => None,
+        }
+    }
+
+    pub fn new_label() -> Self {
+        Actor::Label {
+            offset: Vec2::ZERO,
+            text: "Label".to_owned(),
+            size: 0.5,
+        }
+    }
+
+    /// A rocket motor pushing outwards from the edge starting at `line`, like the one of
+    /// [`prefabs::thruster_cell`](crate::prefabs::thruster_cell)
+    pub fn new_rocket_motor(line: usize) -> Self {
+        Actor::RocketMotor {
+            line,
+            force: vec2(100.0, 0.0),
+            enable: Keybind {
+                activate: vec![KeyCode::W],
+                disable: vec![KeyCode::S],
+                forced: None,
+            },
+            particle_time: 0.0,
+            max_particle_time: 0.005,
+            gimbal: None,
+            exhaust_start: Self::EXHAUST_START_COLOR,
+            exhaust_end: Self::EXHAUST_END_COLOR,
+        }
+    }
+
+    /// Like the one of [`prefabs::reaction_wheel`](crate::prefabs::reaction_wheel)
+    pub fn new_reaction_wheel() -> Self {
+        Actor::ReactionWheel {
+            torque: 20.0,
+            enable_cw: Keybind {
+                activate: vec![KeyCode::E],
+                disable: vec![],
+                forced: None,
+            },
+            enable_ccw: Keybind {
+                activate: vec![KeyCode::Q],
+                disable: vec![],
+                forced: None,
+            },
+        }
+    }
+
+    /// Holds the edge starting at `line` pointing along `target_direction`
+    pub fn new_orientation_spring(line: usize, target_direction: Vec2) -> Self {
+        Actor::OrientationSpring {
+            line,
+            target_direction: target_direction.normalize_or(Vec2::X),
+            stiffness: 20.0,
+            damping: 10.0,
+        }
+    }
+
+    /// Whether every edge and spring this refers to exists on a soft body with `num_points` points
+    /// and `num_internal_springs` internal springs
+    pub fn is_valid(&self, num_points: usize, num_internal_springs: usize) -> bool {
+        match self {
+            Actor::RocketMotor { line, .. } | Actor::OrientationSpring { line, .. } => {
+                *line < num_points
+            }
+            Actor::Piston { lengths, .. } => {
+                !lengths.is_empty()
+                    && lengths.iter().all(|(index, _, _)| match *index {
+                        SpringIndex::Edge(i) => i < num_points,
+                        SpringIndex::Internal(i) => i < num_internal_springs,
+                    })
+            }
+            Actor::HabitatBubble { .. } | Actor::ReactionWheel { .. } | Actor::Label { .. } => true,
         }
     }
 }
@@ -1137,23 +2502,36 @@ impl Actor {
 pub struct Keybind {
     pub activate: Vec<KeyCode>,
     pub disable: Vec<KeyCode>,
+    /// Takes precedence over the keys when set, so actors can be driven without key presses. See
+    /// [`SoftBody::set_actor_enabled`]
+    pub forced: Option<bool>,
 }
 
 impl Keybind {
-    pub fn is_down(&self) -> bool {
-        self.activate_down() && !self.disable_down()
+    /// Ignores `suppressed_keys`, see [`InputState::suppressed_keys`]
+    ///
+    /// [`InputState::suppressed_keys`]: crate::simulation::InputState::suppressed_keys
+    pub fn is_down(&self, suppressed_keys: &[KeyCode]) -> bool {
+        self.forced.unwrap_or_else(|| {
+            self.activate_down(suppressed_keys) && !self.disable_down(suppressed_keys)
+        })
     }
 
-    pub fn activate_down(&self) -> bool {
+    pub fn activate_down(&self, suppressed_keys: &[KeyCode]) -> bool {
         self.activate
             .iter()
-            .any(|&key_code| input::is_key_down(key_code))
+            .any(|&key_code| Self::key_down(key_code, suppressed_keys))
     }
 
-    pub fn disable_down(&self) -> bool {
+    pub fn disable_down(&self, suppressed_keys: &[KeyCode]) -> bool {
         self.disable
             .iter()
-            .any(|&key_code| input::is_key_down(key_code))
+            .any(|&key_code| Self::key_down(key_code, suppressed_keys))
+    }
+
+    /// Like [`input::is_key_down`], but ignores `suppressed_keys`
+    pub fn key_down(key_code: KeyCode, suppressed_keys: &[KeyCode]) -> bool {
+        !suppressed_keys.contains(&key_code) && input::is_key_down(key_code)
     }
 
     pub fn contains(&self, key_code: KeyCode) -> bool {
@@ -1196,29 +2574,112 @@ pub struct LinearSpring {
     pub damping: f32,
     pub compression: bool,
     pub tension: bool,
+    /// The spring force is clamped to this, no matter how far the spring is stretched or
+    /// compressed
     pub maximum_force: f32,
+    /// The relative velocity along the spring is clamped to this before being multiplied by
+    /// `damping`
     pub maximum_damping: f32,
+    /// If either maximum is reached, the owning soft body is reported as unstable by
+    /// [`SoftBody::apply_impulse_and_velocity`] and destroyed by the simulation
     pub destroy_on_maximum: bool,
+    /// The shortest the spring can be compressed to, as a fraction of `target_distance`. Enforced
+    /// by moving the points after they are integrated, so edges can't be crushed past zero length
+    /// and flip the winding of the shape. Zero by default, but [`SoftBodyBuilder`] sets it on the
+    /// edges of the outline, see [`SoftBodyBuilder::minimum_edge_length`].
+    pub minimum_length: f32,
 }
 
 impl LinearSpring {
+    /// Springs which are clamped at their maximum force or damping flash between this and their
+    /// usual color
+    pub const MAXIMUM_COLOR: Color = colors::MAGENTA;
+    /// How many times per second a clamped spring flashes
+    pub const MAXIMUM_FLASH_RATE: f64 = 4.0;
+
     pub fn draw_line(&self, point_a: &Point, point_b: &Point) {
-        let (force, damping, _, _) = self.get_force(point_a, point_b);
+        let (force, damping, _, maximum_reached) = self.get_force(point_a, point_b);
 
-        let color = utils::generate_color_for_spring(force, damping);
+        let color =
+            if maximum_reached && (time::get_time() * Self::MAXIMUM_FLASH_RATE).fract() < 0.5 {
+                Self::MAXIMUM_COLOR
+            } else {
+                utils::generate_color_for_spring(force, damping)
+            };
 
         utils::draw_line(point_a.position, point_b.position, 0.05, color);
     }
 
+    /// Colors the spring by its force as a fraction of the most it can apply
+    pub fn draw_load(&self, point_a: &Point, point_b: &Point) {
+        let (force, _, _, _) = self.get_force(point_a, point_b);
+
+        let load = force.abs() / self.maximum_force;
+
+        utils::draw_line(
+            point_a.position,
+            point_b.position,
+            0.05,
+            utils::generate_color_for_load(load),
+        );
+    }
+
+    /// Returns `true` if the spring reached its maximum and should be destroyed
     pub fn apply_force(&self, point_a: &mut Point, point_b: &mut Point, dt: f32) -> bool {
         let (_, _, impulse, maximum_reached) = self.get_force(point_a, point_b);
 
         point_a.impulse += impulse / 2.0 * dt;
         point_b.impulse -= impulse / 2.0 * dt;
 
-        maximum_reached
+        maximum_reached && self.destroy_on_maximum
+    }
+
+    /// Pushes the points apart along the spring if it is shorter than `minimum_length`, and
+    /// removes the velocity compressing it. Heavier points are moved less.
+    /// `previous_displacement` is from `point_b` to `point_a` before they were integrated, and the
+    /// length is measured along it, so points which passed all the way through each other in one
+    /// tick are pushed back too.
+    pub fn enforce_minimum_length(
+        &self,
+        point_a: &mut Point,
+        point_b: &mut Point,
+        previous_displacement: Vec2,
+    ) {
+        if self.minimum_length <= 0.0 {
+            return;
+        }
+
+        let Some(direction) = previous_displacement.try_normalize() else {
+            return;
+        };
+
+        let minimum_distance = self.target_distance * self.minimum_length;
+        let distance = (point_a.position - point_b.position).dot(direction);
+
+        if distance >= minimum_distance {
+            return;
+        }
+
+        let total_mass = point_a.mass + point_b.mass;
+        let correction = direction * (minimum_distance - distance);
+
+        point_a.position += correction * point_b.mass / total_mass;
+        point_b.position -= correction * point_a.mass / total_mass;
+
+        let normal_velocity = (point_a.velocity - point_b.velocity).dot(direction);
+
+        if normal_velocity < 0.0 {
+            let center_velocity = (point_a.velocity.dot(direction) * point_a.mass
+                + point_b.velocity.dot(direction) * point_b.mass)
+                / total_mass;
+
+            point_a.velocity += direction * (center_velocity - point_a.velocity.dot(direction));
+            point_b.velocity += direction * (center_velocity - point_b.velocity.dot(direction));
+        }
     }
 
+    /// Returns the spring force, the damping force, the total impulse on `point_a`, and whether
+    /// either force was clamped at its maximum
     pub fn get_force(&self, point_a: &Point, point_b: &Point) -> (f32, f32, Vec2, bool) {
         fn clamp(value: f32, range: f32, maximum_reached: &mut bool) -> f32 {
             if value > range {
@@ -1247,12 +2708,11 @@ impl LinearSpring {
         let normal_velocity = relative_velocity.dot(direction);
 
         let force = utils::clamp_sign(
-            self.force_constant
-                * clamp(
-                    self.target_distance - distance,
-                    self.maximum_force,
-                    &mut maximum_reached,
-                ),
+            clamp(
+                self.force_constant * (self.target_distance - distance),
+                self.maximum_force,
+                &mut maximum_reached,
+            ),
             self.compression,
             self.tension,
         );
@@ -1264,12 +2724,7 @@ impl LinearSpring {
 
         let total_force = force + damping;
 
-        (
-            force,
-            damping,
-            direction * total_force,
-            maximum_reached && self.destroy_on_maximum,
-        )
+        (force, damping, direction * total_force, maximum_reached)
     }
 }
 
@@ -1281,15 +2736,19 @@ impl Default for LinearSpring {
             damping: 50.0,
             compression: true,
             tension: true,
-            maximum_force: 0.75,
+            maximum_force: 750.0,
             maximum_damping: 100.0,
             destroy_on_maximum: true,
+            minimum_length: 0.0,
         }
     }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct AngularSpring {
+    /// How far the shape turns at the point, from the direction of the edge before it to the
+    /// direction of the edge after it. Positive at convex corners and negative at concave ones, since
+    /// points are counter clockwise.
     pub target_angle: f32,
     pub force_constant: f32,
     pub damping: f32,
@@ -1338,6 +2797,9 @@ impl AngularSpring {
             return (0.0, 0.0, [Vec2::ZERO; 3]);
         }
 
+        // Wraps around at ±π, so the displacement is taken the short way around. Otherwise a sharp
+        // corner bent just past a straight reversal would be pushed all the way around the other
+        // way, turning the shape inside out.
         let angle = base_direction.angle_between(measure_direction);
 
         let point_a_normal = base_direction.perp() / point_a_distance;
@@ -1352,7 +2814,8 @@ impl AngularSpring {
 
         let force = utils::clamp_sign(
             self.force_constant
-                * (self.target_angle - angle).clamp(-self.maximum_force, self.maximum_force),
+                * utils::angle_difference(angle, self.target_angle)
+                    .clamp(-self.maximum_force, self.maximum_force),
             self.inwards,
             self.outwards,
         );
@@ -1513,12 +2976,14 @@ pub struct BoundingBox {
 
 impl BoundingBox {
     pub fn draw(&self) {
+        let thickness = 0.1 * utils::line_scale();
+
         shapes::draw_rectangle_lines(
-            self.min_corner.x - 0.05,
-            self.min_corner.y - 0.05,
-            self.size.x + 0.1,
-            self.size.y + 0.1,
-            0.1,
+            self.min_corner.x - thickness / 2.0,
+            self.min_corner.y - thickness / 2.0,
+            self.size.x + thickness,
+            self.size.y + thickness,
+            thickness,
             colors::DARKBLUE,
         );
     }
@@ -1555,6 +3020,16 @@ impl BoundingBox {
             && other.min_corner.y <= self.max_corner().y + distance
     }
 
+    /// The smallest bounding box containing both
+    pub fn union(&self, other: &BoundingBox) -> Self {
+        Self::fit_points(&[
+            self.min_corner,
+            self.max_corner(),
+            other.min_corner,
+            other.max_corner(),
+        ])
+    }
+
     pub fn fit_points(points: &[Vec2]) -> Self {
         if points.is_empty() {
             return Self::default();
@@ -1576,6 +3051,30 @@ impl BoundingBox {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoundingCircle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl BoundingCircle {
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.is_point_within_distance(point, 0.0)
+    }
+
+    pub fn is_point_within_distance(&self, point: Vec2, distance: f32) -> bool {
+        self.center.distance_squared(point) <= (self.radius + distance).powi(2)
+    }
+
+    pub fn intersects_other(&self, other: &BoundingCircle) -> bool {
+        self.is_other_within_distance(other, 0.0)
+    }
+
+    pub fn is_other_within_distance(&self, other: &BoundingCircle, distance: f32) -> bool {
+        self.is_point_within_distance(other.center, other.radius + distance)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SoftBodyBuilder {
     pub soft_body: SoftBody,
@@ -1585,9 +3084,16 @@ pub struct SoftBodyBuilder {
     pub base_line: Line,
 
     pub subdivisions: usize,
+    /// See [`Self::subdivision_bracing`]
+    pub subdivision_bracing: Option<LinearSpring>,
+    /// The indecies of the points added by subdivision
+    pub subdivision_points: Vec<usize>,
 
     pub last_spring_specified: bool,
     pub spring_scale: f32,
+    /// The [`LinearSpring::minimum_length`] every edge of the outline gets at least, so the shape
+    /// can't be crushed inside out
+    pub minimum_edge_length: f32,
 }
 
 impl Default for SoftBodyBuilder {
@@ -1600,14 +3106,86 @@ impl Default for SoftBodyBuilder {
             base_line: Line::default(),
 
             subdivisions: 0,
+            subdivision_bracing: None,
+            subdivision_points: Vec::new(),
 
             last_spring_specified: false,
             spring_scale: 1.0,
+            minimum_edge_length: Self::DEFAULT_MINIMUM_EDGE_LENGTH,
         }
     }
 }
 
 impl SoftBodyBuilder {
+    /// See [`Self::minimum_edge_length`]
+    pub const DEFAULT_MINIMUM_EDGE_LENGTH: f32 = 0.1;
+
+    /// A lumpy, roughly round rock of `sides` points centered on the origin, generated the same way
+    /// every time for the same `seed`. Each point's distance from the center is `radius` scaled up
+    /// or down by as much as `roughness`, following smooth value noise around the outline. It is
+    /// heavy, stiff, and braced across its middle, so it keeps its shape when things bump into it.
+    pub fn asteroid(radius: f32, roughness: f32, sides: usize, seed: u64) -> Self {
+        let sides = sides.max(3);
+
+        let rng = rand::RandGenerator::new();
+        rng.srand(seed);
+
+        // Fewer noise values than points, so neighboring points are similar and the outline is
+        // lumpy rather than jagged
+        let noise = (0..sides.div_ceil(3).max(3))
+            .map(|_| rng.gen_range(-1.0, 1.0))
+            .collect::<Vec<f32>>();
+
+        let mut builder = Self::default()
+            .mass(2.0)
+            .friction(1.0)
+            .base_angular_spring(Some(AngularSpring {
+                force_constant: 500.0,
+                damping: 20.0,
+                ..Default::default()
+            }))
+            .base_spring(LinearSpring {
+                force_constant: 5000.0,
+                damping: 100.0,
+                maximum_force: 2500.0,
+                destroy_on_maximum: false,
+                ..Default::default()
+            });
+
+        for i in 0..sides {
+            let progress = i as f32 / sides as f32;
+
+            let position = progress * noise.len() as f32;
+            let index = position as usize;
+            let t = position.fract();
+            let t = t * t * (3.0 - 2.0 * t);
+            let noise = utils::lerp(noise[index], noise[(index + 1) % noise.len()], t);
+
+            let angle = progress * TAU;
+            let distance = radius * (1.0 + roughness * noise).max(0.1);
+
+            builder = builder.point(angle.cos() * distance, angle.sin() * distance);
+        }
+
+        for i in 0..sides / 2 {
+            let j = i + sides / 2;
+            let distance = builder.soft_body.shape[i]
+                .0
+                .position
+                .distance(builder.soft_body.shape[j].0.position);
+
+            builder.soft_body.internal_springs.push((
+                [i, j],
+                LinearSpring {
+                    target_distance: distance,
+                    ..builder.base_line.spring
+                },
+            ));
+        }
+
+        builder
+    }
+
     pub fn build(mut self) -> SoftBody {
         assert!(self.soft_body.shape.len() >= 3, "Not enough points");
 
@@ -1615,6 +3193,10 @@ impl SoftBodyBuilder {
         self.add_subdivisions(first_position);
         self.fix_last_spring(first_position);
 
+        if let Some(spring) = self.subdivision_bracing {
+            self.add_subdivision_bracing(spring);
+        }
+
         for (id, internal_spring) in self.internal_springs.into_iter().enumerate() {
             match internal_spring {
                 InternalSpringBuilder::Incomplete(_) => panic!("Spring {id} is incomplete"),
@@ -1622,6 +3204,10 @@ impl SoftBodyBuilder {
             }
         }
 
+        for (_, line) in &mut self.soft_body.shape {
+            line.spring.minimum_length = line.spring.minimum_length.max(self.minimum_edge_length);
+        }
+
         for i in 0..self.soft_body.shape.len() {
             let [point_a, point_b, point_c] = self.soft_body.get_angle_mut(i).unwrap();
 
@@ -1637,8 +3223,18 @@ impl SoftBodyBuilder {
             if attatchment_point.start_point >= self.soft_body.shape.len() {
                 panic!("Unused attatchment point {attatchment_point:?}");
             }
+
+            // Seams are walked with wrapping indices, so they can't be empty or overlap themselves
+            if attatchment_point.length == 0
+                || attatchment_point.length > self.soft_body.shape.len()
+            {
+                panic!("Attatchment point {attatchment_point:?} has an invalid length");
+            }
         }
 
+        self.soft_body.update_bounding_box();
+        self.soft_body.update_triangulation_indecies();
+
         self.soft_body
     }
 
@@ -1667,24 +3263,58 @@ impl SoftBodyBuilder {
     }
 
     fn add_subdivisions(&mut self, point: Vec2) {
-        if self.subdivisions > 0 {
-            if let Some(&(Point { position, .. }, _)) = self.soft_body.shape.last() {
-                let segments = self.subdivisions + 1;
+        if self.subdivisions > 0
+            && let Some(&(Point { position, .. }, _)) = self.soft_body.shape.last()
+        {
+            let segments = self.subdivisions + 1;
+
+            for i in 1..segments {
+                self.point_inner(position.lerp(point, i as f32 / segments as f32));
+                self.subdivision_points.push(self.soft_body.shape.len() - 1);
+            }
+        }
+    }
 
-                for i in 1..segments {
-                    self.point_inner(position.lerp(point, i as f32 / segments as f32));
-                }
+    /// Links each point added by subdivision to the point halfway around the shape from it, so
+    /// finely subdivided shapes are held open across their middle instead of only at their
+    /// corners. Pairs which are already linked or next to each other are skipped.
+    fn add_subdivision_bracing(&mut self, spring: LinearSpring) {
+        let len = self.soft_body.shape.len();
+
+        for &i in &self.subdivision_points {
+            let j = (i + len / 2) % len;
+
+            if j == i
+                || j == self.soft_body.next_point(i)
+                || i == self.soft_body.next_point(j)
+                || self
+                    .soft_body
+                    .internal_springs
+                    .iter()
+                    .any(|&([a, b], _)| [a, b] == [i, j] || [a, b] == [j, i])
+            {
+                continue;
             }
+
+            let distance =
+                (self.soft_body.shape[i].0.position).distance(self.soft_body.shape[j].0.position);
+
+            self.soft_body.internal_springs.push((
+                [i, j],
+                LinearSpring {
+                    target_distance: distance * self.spring_scale,
+                    ..spring
+                },
+            ));
         }
     }
 
     fn fix_last_spring(&mut self, point: Vec2) {
-        if !self.last_spring_specified {
-            if let Some(&mut (Point { position, .. }, Line { ref mut spring, .. })) =
+        if !self.last_spring_specified
+            && let Some(&mut (Point { position, .. }, Line { ref mut spring, .. })) =
                 self.soft_body.shape.last_mut()
-            {
-                spring.target_distance = position.distance(point) * self.spring_scale;
-            }
+        {
+            spring.target_distance = position.distance(point) * self.spring_scale;
         }
     }
 
@@ -1694,6 +3324,12 @@ impl SoftBodyBuilder {
         self
     }
 
+    /// Overrides the mass of the last point added, leaving the others at [`Self::mass`]
+    pub fn with_point_mass(mut self, mass: f32) -> Self {
+        self.soft_body.shape.last_mut().unwrap().0.mass = mass.max(Point::MINIMUM_MASS);
+        self
+    }
+
     pub fn with_spring_length(mut self, length: f32) -> Self {
         self.soft_body
             .shape
@@ -1750,8 +3386,12 @@ impl SoftBodyBuilder {
         self
     }
 
+    /// The mass is clamped to at least [`Point::MINIMUM_MASS`]
     pub fn base_point(mut self, point: Point) -> Self {
-        self.base_point = point;
+        self.base_point = Point {
+            mass: point.mass.max(Point::MINIMUM_MASS),
+            ..point
+        };
         self
     }
 
@@ -1760,8 +3400,9 @@ impl SoftBodyBuilder {
         self
     }
 
+    /// Clamped to at least [`Point::MINIMUM_MASS`]
     pub fn mass(mut self, mass: f32) -> Self {
-        self.base_point.mass = mass;
+        self.base_point.mass = mass.max(Point::MINIMUM_MASS);
         self
     }
 
@@ -1778,47 +3419,96 @@ impl SoftBodyBuilder {
         self.offset_ex(vec2(x, y))
     }
 
+    /// Points added before this are moved along with the offset
     pub fn offset_ex(mut self, offset: Vec2) -> Self {
+        let delta = offset - self.base_point.position;
+
+        for (point, _) in &mut self.soft_body.shape {
+            point.position += delta;
+        }
+
         self.base_point.position = offset;
         self
     }
 
+    /// Adds `subdivisions` evenly spaced points along each edge added after this, so the edge bends
+    /// smoothly. Every new edge's spring rests at the length of its own segment times the spring
+    /// scale, unless it is overridden with [`Self::with_spring`] or [`Self::with_spring_length`],
+    /// which only apply to the last segment of the edge.
     pub fn subdivisions(mut self, subdivisions: usize) -> Self {
         self.subdivisions = subdivisions;
         self
     }
 
+    /// Braces the points added by [`Self::subdivisions`] across the shape with copies of `spring`,
+    /// so subdivided shapes don't collapse. See [`Self::add_subdivision_bracing`]
+    pub fn subdivision_bracing(mut self, spring: LinearSpring) -> Self {
+        self.subdivision_bracing = Some(spring);
+        self
+    }
+
     pub fn gas_force(mut self, gas_force: f32) -> Self {
         self.soft_body.gas_force = gas_force;
         self
     }
 
+    pub fn collision_layer(mut self, collision_layer: u32) -> Self {
+        self.soft_body.collision_layer = collision_layer;
+        self
+    }
+
+    pub fn collision_mask(mut self, collision_mask: u32) -> Self {
+        self.soft_body.collision_mask = collision_mask;
+        self
+    }
+
+    /// See [`SoftBody::health`]
+    pub fn health(mut self, health: f32) -> Self {
+        self.soft_body.health = health;
+        self
+    }
+
+    /// See [`SoftBody::id`]
+    pub fn id(mut self, id: u64) -> Self {
+        self.soft_body.id = Some(id);
+        self
+    }
+
     pub fn spring_scale(mut self, spring_scale: f32) -> Self {
         self.spring_scale = spring_scale;
         self
     }
 
+    pub fn minimum_edge_length(mut self, minimum_edge_length: f32) -> Self {
+        self.minimum_edge_length = minimum_edge_length;
+        self
+    }
+
     pub fn friction(mut self, friction: f32) -> Self {
         self.base_line.friction = friction;
         self
     }
 
+    /// Starts a seam of `length` points at the last point added. A seam started near the end of the
+    /// shape wraps around past the last point to the first.
     pub fn with_attatchment_point(mut self, length: usize) -> Self {
         self.soft_body.attatchment_points.push(AttatchmentPoint {
             start_point: self.soft_body.shape.len().checked_sub(1).unwrap(),
             length,
-            connection: None,
+            connections: Vec::new(),
         });
         self
     }
 
     pub fn with_actor(mut self, mut actor: Actor) -> Self {
         match &mut actor {
-            Actor::RocketMotor { line, .. } => {
+            Actor::RocketMotor { line, .. } | Actor::OrientationSpring { line, .. } => {
                 *line = self.soft_body.shape.len() - 1;
             }
             Actor::HabitatBubble { .. } => (),
             Actor::Piston { .. } => (),
+            Actor::ReactionWheel { .. } => (),
+            Actor::Label { .. } => (),
         }
         self.soft_body.actors.push(actor);
         self
@@ -1836,3 +3526,427 @@ pub enum InternalSpringBuilder {
     Incomplete(usize),
     Complete,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The kinetic energy of both points and the potential energy of an undamped `spring` between
+    /// them
+    fn spring_energy(spring: &LinearSpring, point_a: &Point, point_b: &Point) -> f32 {
+        let stretch = point_a.position.distance(point_b.position) - spring.target_distance;
+
+        0.5 * point_a.mass * point_a.velocity.length_squared()
+            + 0.5 * point_b.mass * point_b.velocity.length_squared()
+            + 0.5 * spring.force_constant * stretch.powi(2)
+    }
+
+    /// The relative change in energy of an undamped spring oscillating for ten seconds
+    fn spring_energy_drift(integrator: Integrator) -> f32 {
+        let spring = LinearSpring {
+            damping: 0.0,
+            maximum_force: f32::INFINITY,
+            destroy_on_maximum: false,
+            ..Default::default()
+        };
+
+        let mut point_a = Point {
+            position: vec2(1.2, 0.0),
+            ..Default::default()
+        };
+        let mut point_b = Point::default();
+
+        let dt = 1.0 / 120.0;
+        let start = spring_energy(&spring, &point_a, &point_b);
+
+        for _ in 0..1200 {
+            spring.apply_force(&mut point_a, &mut point_b, dt);
+            point_a.apply_impulse_and_velocity(dt, integrator);
+            point_b.apply_impulse_and_velocity(dt, integrator);
+        }
+
+        (spring_energy(&spring, &point_a, &point_b) - start) / start
+    }
+
+    #[test]
+    fn spring_force_is_clamped_and_breaks_past_its_maximum() {
+        let spring = LinearSpring {
+            force_constant: 100.0,
+            damping: 0.0,
+            maximum_force: 50.0,
+            ..Default::default()
+        };
+
+        let mut point_a = Point {
+            position: vec2(1.4, 0.0),
+            ..Default::default()
+        };
+        let mut point_b = Point::default();
+
+        let (force, _, _, maximum_reached) = spring.get_force(&point_a, &point_b);
+        assert!((force + 40.0).abs() < 1e-3, "force was {force}");
+        assert!(!maximum_reached);
+        assert!(!spring.apply_force(&mut point_a, &mut point_b, 1.0 / 120.0));
+
+        point_a.position = vec2(1.6, 0.0);
+
+        let (force, _, _, maximum_reached) = spring.get_force(&point_a, &point_b);
+        assert_eq!(force, -50.0);
+        assert!(maximum_reached);
+        assert!(spring.apply_force(&mut point_a, &mut point_b, 1.0 / 120.0));
+
+        let unbreakable = LinearSpring {
+            destroy_on_maximum: false,
+            ..spring
+        };
+        assert!(!unbreakable.apply_force(&mut point_a, &mut point_b, 1.0 / 120.0));
+    }
+
+    #[test]
+    fn crushed_triangle_doesnt_invert() {
+        let mut soft_body = SoftBodyBuilder::default()
+            .point(0.0, 0.0)
+            .point(1.0, 0.0)
+            .point(0.5, 1.0)
+            .build();
+
+        let dt = 1.0 / 120.0;
+
+        for _ in 0..240 {
+            // Crushes the bottom edge along its own length
+            let direction =
+                (soft_body.shape[1].0.position - soft_body.shape[0].0.position).normalize_or_zero();
+
+            soft_body.shape[0].0.impulse += direction * 10000.0 * dt;
+            soft_body.shape[1].0.impulse -= direction * 10000.0 * dt;
+
+            let _ = soft_body.apply_impulse_and_velocity(dt, Integrator::default(), &[]);
+
+            assert!(soft_body.area() > 0.0, "area was {}", soft_body.area());
+        }
+    }
+
+    #[test]
+    fn semi_implicit_euler_doesnt_drift() {
+        let drift = spring_energy_drift(Integrator::SemiImplicitEuler);
+
+        assert!(drift.abs() < 0.5, "drift was {drift}");
+    }
+
+    #[test]
+    fn leapfrog_drifts_upwards() {
+        let leapfrog = spring_energy_drift(Integrator::Leapfrog);
+        let semi_implicit_euler = spring_energy_drift(Integrator::SemiImplicitEuler);
+
+        assert!(
+            leapfrog > semi_implicit_euler.abs(),
+            "leapfrog drift was {leapfrog}, semi implicit euler drift was {semi_implicit_euler}",
+        );
+    }
+
+    #[test]
+    fn simplified_keeps_mass_momentum_and_rough_area() {
+        let mut builder = SoftBodyBuilder::default();
+
+        for i in 0..32 {
+            let position = Vec2::from_angle(i as f32 / 32.0 * TAU) * 2.0;
+            builder = builder.point(position.x, position.y);
+        }
+
+        let mut soft_body = builder.build();
+
+        for (i, (point, _)) in soft_body.shape.iter_mut().enumerate() {
+            point.mass = 1.0 + (i % 3) as f32;
+            point.velocity = vec2(1.0, -0.5) + point.position.perp();
+        }
+
+        let mass = |soft_body: &SoftBody| {
+            soft_body
+                .shape
+                .iter()
+                .map(|(point, _)| point.mass)
+                .sum::<f32>()
+        };
+        let momentum = |soft_body: &SoftBody| {
+            soft_body
+                .shape
+                .iter()
+                .map(|(point, _)| point.velocity * point.mass)
+                .sum::<Vec2>()
+        };
+
+        let simplified = soft_body.simplified(8);
+
+        assert_eq!(simplified.shape.len(), 8);
+        assert!((mass(&simplified) - mass(&soft_body)).abs() < 1e-3);
+        assert!(momentum(&simplified).distance(momentum(&soft_body)) < 1e-2);
+
+        let area_ratio = simplified.area() / soft_body.area();
+        assert!(
+            (0.75..=1.0).contains(&area_ratio),
+            "area ratio was {area_ratio}"
+        );
+
+        assert_eq!(soft_body.simplified(1).shape.len(), 3);
+    }
+
+    /// The total unsigned area of the triangles, which only matches the area of the shape if
+    /// they cover it without overlapping
+    fn triangulated_area(soft_body: &SoftBody) -> f32 {
+        soft_body
+            .triangle_indices
+            .chunks_exact(3)
+            .map(|chunk| {
+                let [a, b, c] = [chunk[0], chunk[1], chunk[2]]
+                    .map(|index| soft_body.shape[index as usize].0.position);
+
+                (b - a).perp_dot(c - a).abs() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn concave_shapes_are_triangulated_without_stray_triangles() {
+        let soft_body = SoftBodyBuilder::default()
+            .point(0.0, 0.0)
+            .point(3.0, 0.0)
+            .point(3.0, 3.0)
+            .point(2.0, 3.0)
+            .point(2.0, 1.0)
+            .point(1.0, 1.0)
+            .point(1.0, 3.0)
+            .point(0.0, 3.0)
+            .build();
+
+        assert_eq!(soft_body.triangle_indices.len(), 3 * 6);
+        assert!(!soft_body.has_inverted_triangles());
+        assert!((soft_body.area() - 7.0).abs() < 1e-4);
+        assert!((triangulated_area(&soft_body) - 7.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn deforming_into_a_concave_shape_triangulates_again() {
+        let mut soft_body = SoftBodyBuilder::default()
+            .point(0.0, 0.0)
+            .point(2.0, 0.0)
+            .point(2.0, 2.0)
+            .point(1.0, 2.0)
+            .point(0.0, 2.0)
+            .build();
+
+        // Dents the top edge in almost to the bottom edge
+        soft_body.shape[3].0.position = vec2(1.0, 0.2);
+        assert!(soft_body.has_inverted_triangles());
+
+        soft_body.update_triangulation_indecies();
+
+        assert!(!soft_body.has_inverted_triangles());
+        assert!((triangulated_area(&soft_body) - soft_body.area()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn heavy_point_pulls_the_center_of_mass_towards_it() {
+        let soft_body = SoftBodyBuilder::default()
+            .mass(1.0)
+            .point(0.0, 0.0)
+            .point(2.0, 0.0)
+            .with_point_mass(10.0)
+            .point(2.0, 2.0)
+            .point(0.0, 2.0)
+            .build();
+
+        let masses = soft_body
+            .shape
+            .iter()
+            .map(|(point, _)| point.mass)
+            .collect::<Vec<_>>();
+        assert_eq!(masses, [1.0, 10.0, 1.0, 1.0]);
+
+        let heavy_point = vec2(2.0, 0.0);
+        let center_of_mass = soft_body.calculate_center_of_mass();
+
+        assert!(
+            center_of_mass.distance(heavy_point)
+                < soft_body.calculate_centroid().distance(heavy_point),
+            "center of mass was {center_of_mass}",
+        );
+        assert!(center_of_mass.distance(vec2(22.0, 4.0) / 13.0) < 1e-4);
+    }
+
+    #[test]
+    fn subdivided_edges_rest_at_the_length_of_their_segments() {
+        let soft_body = SoftBodyBuilder::default()
+            .spring_scale(0.5)
+            .subdivisions(2)
+            .point(0.0, 0.0)
+            .point(3.0, 0.0)
+            .point(3.0, 3.0)
+            .point(0.0, 3.0)
+            .subdivision_bracing(LinearSpring::default())
+            .build();
+
+        assert_eq!(soft_body.shape.len(), 4 * 3);
+
+        for (point_a, line, point_b) in soft_body.edges() {
+            assert!((point_a.position.distance(point_b.position) - 1.0).abs() < 1e-4);
+            assert!(
+                (line.spring.target_distance - 0.5).abs() < 1e-4,
+                "spring rested at {}",
+                line.spring.target_distance,
+            );
+        }
+
+        // Every subdivision point is braced to the one opposite it
+        assert_eq!(soft_body.internal_springs.len(), 4);
+
+        for &([a, b], spring) in &soft_body.internal_springs {
+            assert_eq!((a + soft_body.shape.len() / 2) % soft_body.shape.len(), b);
+
+            let distance = soft_body.shape[a]
+                .0
+                .position
+                .distance(soft_body.shape[b].0.position);
+            assert!((spring.target_distance - distance * 0.5).abs() < 1e-4);
+        }
+    }
+
+    /// How far the shape turns at each point, negative at concave corners
+    fn turn_angles(soft_body: &SoftBody) -> Vec<f32> {
+        soft_body
+            .angles()
+            .map(|[a, b, c]| (b.position - a.position).angle_between(c.position - b.position))
+            .collect()
+    }
+
+    #[test]
+    fn squashed_star_holds_its_concave_corners() {
+        let mut builder = SoftBodyBuilder::default();
+
+        for i in 0..10 {
+            let radius = if i % 2 == 0 { 2.0 } else { 0.3 };
+            let position = Vec2::from_angle(i as f32 / 10.0 * TAU) * radius;
+
+            builder = builder.point(position.x, position.y);
+        }
+
+        let mut soft_body = builder.build();
+
+        let rest_angles = turn_angles(&soft_body);
+        let rest_area = soft_body.area();
+        assert!(
+            rest_angles
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .all(|&angle| angle < 0.0)
+        );
+
+        let dt = 1.0 / 120.0;
+
+        for tick in 0..480 {
+            // Squashes the star flat for the first second, then lets it spring back
+            if tick < 120 {
+                for (point, _) in &mut soft_body.shape {
+                    point.impulse.y -= point.position.y * 200.0 * dt;
+                }
+            }
+
+            let _ = soft_body.apply_impulse_and_velocity(dt, Integrator::default(), &[]);
+        }
+
+        assert!(!soft_body.is_self_intersecting());
+
+        for (angle, rest_angle) in turn_angles(&soft_body).into_iter().zip(rest_angles) {
+            assert!(
+                angle.signum() == rest_angle.signum(),
+                "a corner which rested at {rest_angle} turned to {angle}",
+            );
+        }
+
+        let area = soft_body.area();
+        assert!(
+            (area - rest_area).abs() < rest_area * 0.2,
+            "area changed from {rest_area} to {area}",
+        );
+    }
+
+    #[test]
+    fn shapes_lying_across_each_other_overlap() {
+        let rectangle = |size: Vec2, offset: Vec2| {
+            SoftBodyBuilder::default()
+                .offset_ex(offset)
+                .point(0.0, 0.0)
+                .point(size.x, 0.0)
+                .point(size.x, size.y)
+                .point(0.0, size.y)
+                .build()
+        };
+
+        // A plus sign, with no corner of either rectangle inside the other
+        let horizontal = rectangle(vec2(3.0, 1.0), vec2(0.0, 1.0));
+        let vertical = rectangle(vec2(1.0, 3.0), vec2(1.0, 0.0));
+
+        assert!(horizontal.overlaps(&vertical));
+        assert!(vertical.overlaps(&horizontal));
+
+        let apart = rectangle(vec2(1.0, 3.0), vec2(4.0, 0.0));
+
+        assert!(!horizontal.overlaps(&apart));
+    }
+
+    #[test]
+    fn pressure_pushes_every_point_outwards() {
+        let mut soft_body = SoftBodyBuilder::default()
+            .point(0.0, 0.0)
+            .point(2.0, 0.0)
+            .point(2.0, 1.0)
+            .point(0.0, 1.0)
+            .build();
+        soft_body.gas_force = 4.0;
+
+        soft_body.add_pressure_impulse(0.5);
+
+        // Counterclockwise, so the outwards normal of an edge is its direction turned clockwise
+        let pressure = 4.0 / 2.0;
+        let expected = [
+            vec2(-0.5, -1.0),
+            vec2(0.5, -1.0),
+            vec2(0.5, 1.0),
+            vec2(-0.5, 1.0),
+        ];
+
+        for ((point, _), expected) in soft_body.shape.iter().zip(expected) {
+            assert!(
+                point.impulse.distance(expected * pressure * 0.5) < 1e-5,
+                "impulse is {}",
+                point.impulse,
+            );
+        }
+    }
+
+    #[test]
+    fn bounding_circles_rule_out_round_shapes_touching_at_the_corners() {
+        let octagon = |offset: Vec2| {
+            let mut builder = SoftBodyBuilder::default().offset_ex(offset);
+            for i in 0..8 {
+                let angle = i as f32 / 8.0 * std::f32::consts::TAU;
+                builder = builder.point(angle.cos(), angle.sin());
+            }
+            let mut soft_body = builder.build();
+            soft_body.update_bounding_box();
+            soft_body
+        };
+
+        // Diagonal neighbours, so the corners of their bounding boxes overlap
+        let first = octagon(Vec2::ZERO);
+        let second = octagon(vec2(1.8, 1.8));
+
+        assert!(first.bounding_box.intersects_other(&second.bounding_box));
+        assert!(
+            !first
+                .bounding_circle()
+                .intersects_other(&second.bounding_circle())
+        );
+        assert!(!first.overlaps(&second));
+    }
+}
@@ -1,46 +1,106 @@
 use std::{
     array,
     f32::consts::{SQRT_2, TAU},
+    ops::Range,
 };
 
 use macroquad::{
-    color::colors,
+    color::{Color, colors},
     rand,
     shapes::{self, DrawRectangleParams},
 };
 use nalgebra::{Isometry2, Point2, Vector2};
-use ndarray::Array2;
+use ndarray::{Array2, s};
+
+use crate::utils;
 
 pub const STAR_MAP_SIZE: f32 = 1000.0;
 
 pub const STAR_MAP_BUCKET_SIZE: f32 = 10.0;
 
 pub const STAR_DENSITY: f32 = 0.025;
-pub const NUM_STARS: u64 = (STAR_MAP_SIZE * STAR_MAP_SIZE * STAR_DENSITY) as u64;
+
+#[derive(Clone, Copy, Debug)]
+pub struct StarFieldConfig {
+    /// The width and height of the area the stars repeat over
+    pub map_size: f32,
+    /// Stars per square unit
+    pub density: f32,
+    pub star_size: f32,
+    /// Whether to draw a nebula behind the stars
+    pub nebula: bool,
+}
+
+impl Default for StarFieldConfig {
+    fn default() -> Self {
+        Self {
+            map_size: STAR_MAP_SIZE,
+            density: STAR_DENSITY,
+            star_size: Star::SIZE,
+            nebula: true,
+        }
+    }
+}
+
+impl StarFieldConfig {
+    pub fn num_stars(&self) -> u64 {
+        (self.map_size * self.map_size * self.density) as u64
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StarField {
+    pub stars: PointSet<Star>,
+    pub config: StarFieldConfig,
+    pub seed: u64,
+    /// Where the origin of the star field is in the world. See [`AddOffset`]
+    pub offset: Vector2<f32>,
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct Star {
     pub position: Isometry2<f32>,
+    /// The maximum alpha of the star
+    pub brightness: f32,
+    /// Offset of the twinkle, so that stars don't all twinkle together
+    pub phase: f32,
+    pub tint: Color,
 }
 
-pub fn from_seed(seed: u64) -> PointSet<Star> {
-    rand::srand(seed);
+pub fn from_seed(seed: u64, config: StarFieldConfig) -> StarField {
+    // A local generator, so the global one used for gameplay isn't reset by the star field
+    let rng = rand::RandGenerator::new();
+    rng.srand(seed);
 
     let mut stars = PointSet::new(
-        [(STAR_MAP_SIZE / STAR_MAP_BUCKET_SIZE).ceil() as usize; 2],
+        [(config.map_size / STAR_MAP_BUCKET_SIZE).ceil() as usize; 2],
         STAR_MAP_BUCKET_SIZE,
-        [-STAR_MAP_SIZE / 2.0; 2].into(),
+        [-config.map_size / 2.0; 2].into(),
     );
 
-    for _ in 0..NUM_STARS {
-        stars.insert(Star::random()).unwrap();
+    for _ in 0..config.num_stars() {
+        stars.insert(Star::random(config.map_size, &rng)).unwrap();
     }
 
-    stars
+    StarField {
+        stars,
+        config,
+        seed,
+        offset: Vector2::zeros(),
+    }
 }
 
-pub fn draw_stars_in_area(stars: &PointSet<Star>, area: [Point2<f32>; 2]) {
-    let modular_area = area.map(|point| point.map(to_star_space));
+pub fn draw_stars_in_area(star_field: &StarField, area: [Point2<f32>; 2], time: f32) {
+    let StarField {
+        stars,
+        config,
+        offset,
+        ..
+    } = star_field;
+
+    let area = area.map(|point| point - offset);
+
+    let modular_area = area.map(|point| point.map(|x| to_star_space(x, config.map_size)));
 
     let [min_corner, max_corner] = modular_area.map(|corner| stars.index_of(corner).unwrap());
 
@@ -51,7 +111,7 @@ pub fn draw_stars_in_area(stars: &PointSet<Star>, area: [Point2<f32>; 2]) {
 
         loop {
             for star in &stars.points[[x, y]] {
-                star.draw(area);
+                star.draw(area, *offset, config, time);
             }
 
             if y == max_corner[1] {
@@ -71,58 +131,191 @@ pub fn draw_stars_in_area(stars: &PointSet<Star>, area: [Point2<f32>; 2]) {
     }
 }
 
-pub fn to_star_space(value: f32) -> f32 {
-    (value + STAR_MAP_SIZE / 2.0).rem_euclid(STAR_MAP_SIZE) - STAR_MAP_SIZE / 2.0
+pub const NEBULA_CELL_SIZE: f32 = 40.0;
+/// How much the nebula moves relative to the camera, making it look further away than the stars
+pub const NEBULA_PARALLAX: f32 = 0.5;
+pub const NEBULA_OPACITY: f32 = 0.2;
+/// The nebula is drawn in square tiles, at least this many per screen height
+pub const NEBULA_TILES_PER_SCREEN: f32 = 24.0;
+pub const NEBULA_COLORS: [Color; 2] = [
+    Color::new(0.4, 0.1, 0.6, 1.0),
+    Color::new(0.1, 0.4, 0.6, 1.0),
+];
+
+/// Draws value noise as tiles of color over `area`. Does nothing if [`StarFieldConfig::nebula`] is
+/// disabled.
+pub fn draw_nebula_in_area(star_field: &StarField, area: [Point2<f32>; 2]) {
+    let StarField {
+        config,
+        seed,
+        offset,
+        ..
+    } = *star_field;
+
+    if !config.nebula {
+        return;
+    }
+
+    // Powers of two, so that the tiles line up while zooming
+    let tile_size = ((area[1].y - area[0].y) / NEBULA_TILES_PER_SCREEN)
+        .log2()
+        .ceil()
+        .exp2();
+
+    let [min_x, min_y] = [0, 1].map(|i| (area[0][i] / tile_size).floor() as i64);
+    let [max_x, max_y] = [0, 1].map(|i| (area[1][i] / tile_size).ceil() as i64);
+
+    for x in min_x..max_x {
+        for y in min_y..max_y {
+            let corner = Point2::new(x as f32, y as f32) * tile_size;
+            let sample = (corner - offset + Vector2::from([tile_size / 2.0; 2])) * NEBULA_PARALLAX;
+
+            let density = nebula_noise(sample, config.map_size, seed);
+            let hue = nebula_noise(sample, config.map_size, seed.wrapping_add(1));
+
+            let color = utils::color_lerp(NEBULA_COLORS[0], NEBULA_COLORS[1], hue);
+
+            shapes::draw_rectangle(
+                corner.x,
+                corner.y,
+                tile_size,
+                tile_size,
+                Color {
+                    a: density * density * NEBULA_OPACITY,
+                    ..color
+                },
+            );
+        }
+    }
+}
+
+/// Smoothly interpolated value noise between `0.0` and `1.0`, which repeats every `map_size`
+fn nebula_noise(position: Point2<f32>, map_size: f32, seed: u64) -> f32 {
+    let cells = (map_size / NEBULA_CELL_SIZE).round().max(1.0) as i64;
+
+    let position =
+        position.map(|x| (to_star_space(x, map_size) + map_size / 2.0) / map_size) * cells as f32;
+
+    let cell = position.map(|x| x.floor());
+    let fraction = (position - cell).map(|x| x * x * (3.0 - 2.0 * x));
+
+    let value = |offset_x: i64, offset_y: i64| {
+        let x = (cell.x as i64 + offset_x).rem_euclid(cells);
+        let y = (cell.y as i64 + offset_y).rem_euclid(cells);
+
+        lattice_value(x, y, seed)
+    };
+
+    let top = value(0, 0) + (value(1, 0) - value(0, 0)) * fraction.x;
+    let bottom = value(0, 1) + (value(1, 1) - value(0, 1)) * fraction.x;
+
+    top + (bottom - top) * fraction.y
+}
+
+/// A pseudo-random value between `0.0` and `1.0` for each point of the noise lattice
+fn lattice_value(x: i64, y: i64, seed: u64) -> f32 {
+    let mut hash = seed
+        ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    hash ^= hash >> 33;
+
+    (hash >> 40) as f32 / (1u64 << 24) as f32
+}
+
+pub fn to_star_space(value: f32, map_size: f32) -> f32 {
+    (value + map_size / 2.0).rem_euclid(map_size) - map_size / 2.0
 }
 
 impl Star {
     pub const SIZE: f32 = 1.0 / 12.0;
 
-    pub fn random() -> Self {
+    pub const MINIMUM_BRIGHTNESS: f32 = 0.4;
+    /// The fraction of its brightness a star loses at the bottom of a twinkle
+    pub const TWINKLE_AMOUNT: f32 = 0.3;
+    /// Radians per second
+    pub const TWINKLE_SPEED: f32 = 2.0;
+
+    pub const TINTS: [Color; 3] = [
+        colors::WHITE,
+        Color::new(0.8, 0.85, 1.0, 1.0),
+        Color::new(1.0, 0.95, 0.75, 1.0),
+    ];
+
+    pub fn random(map_size: f32, rng: &rand::RandGenerator) -> Self {
         Self {
             position: Isometry2::new(
-                array::from_fn(|_| rand::gen_range(-STAR_MAP_SIZE / 2.0, STAR_MAP_SIZE / 2.0))
-                    .into(),
-                rand::gen_range(0.0, TAU),
+                array::from_fn(|_| rng.gen_range(-map_size / 2.0, map_size / 2.0)).into(),
+                rng.gen_range(0.0, TAU),
             ),
+            brightness: rng.gen_range(Self::MINIMUM_BRIGHTNESS, 1.0),
+            phase: rng.gen_range(0.0, TAU),
+            tint: Self::TINTS[rng.gen_range(0, Self::TINTS.len())],
         }
     }
 
-    pub fn draw(self, area: [Point2<f32>; 2]) {
+    /// `area` is relative to the star field, which is drawn with its origin at `origin`
+    pub fn draw(
+        self,
+        area: [Point2<f32>; 2],
+        origin: Vector2<f32>,
+        config: &StarFieldConfig,
+        time: f32,
+    ) {
+        let size = config.star_size;
+
         let position = Point2::from(self);
 
         let offset = position - area[0];
 
-        let offset = offset.map(to_star_space);
+        let offset = offset.map(|x| to_star_space(x, config.map_size));
 
         let position = area[0] + offset;
 
-        if position.x + Self::SIZE / SQRT_2 < area[0].x
-            || position.y + Self::SIZE / SQRT_2 < area[0].y
-            || position.x - Self::SIZE / SQRT_2 > area[1].x
-            || position.y - Self::SIZE / SQRT_2 > area[1].y
+        if position.x + size / SQRT_2 < area[0].x
+            || position.y + size / SQRT_2 < area[0].y
+            || position.x - size / SQRT_2 > area[1].x
+            || position.y - size / SQRT_2 > area[1].y
         {
             return;
         }
 
+        let position = position + origin;
+
         #[allow(clippy::unnecessary_cast)]
         shapes::draw_rectangle_ex(
             position.x as f32,
             position.y as f32,
-            Self::SIZE as f32,
-            Self::SIZE as f32,
+            size as f32,
+            size as f32,
             DrawRectangleParams {
                 offset: [0.5, 0.5].into(),
                 rotation: self.position.rotation.angle() as f32,
-                color: colors::WHITE,
+                color: Color {
+                    a: self.alpha(time),
+                    ..self.tint
+                },
             },
         );
     }
+
+    pub fn alpha(self, time: f32) -> f32 {
+        let twinkle = (time * Self::TWINKLE_SPEED + self.phase).sin() * 0.5 + 0.5;
+
+        self.brightness * (1.0 - twinkle * Self::TWINKLE_AMOUNT)
+    }
 }
 
 impl From<Isometry2<f32>> for Star {
     fn from(position: Isometry2<f32>) -> Self {
-        Self { position }
+        Self {
+            position,
+            brightness: 1.0,
+            phase: 0.0,
+            tint: colors::WHITE,
+        }
     }
 }
 
@@ -174,6 +367,62 @@ impl<T> PointSet<T> {
         Some(index)
     }
 
+    /// Clears every bucket and inserts each point again, returning how many were out of bounds.
+    ///
+    /// Costs O(buckets + points), so it is simpler but slower than [`Self::update_position`] when
+    /// only a few points have moved.
+    pub fn rebuild_from(&mut self, points: impl IntoIterator<Item = T>) -> usize
+    where
+        T: Into<Point2<f32>> + Clone,
+    {
+        self.clear();
+
+        points
+            .into_iter()
+            .filter(|point| self.insert(point.clone()).is_none())
+            .count()
+    }
+
+    /// Removes the first point equal to `point` from the bucket at its position.
+    ///
+    /// Costs O(bucket length). Won't find a point which has moved since it was inserted.
+    pub fn remove(&mut self, point: &T) -> Option<T>
+    where
+        T: Into<Point2<f32>> + Clone + PartialEq,
+    {
+        let index = self.index_of(point.clone().into())?;
+        let bucket = &mut self.points[index];
+        let i = bucket.iter().position(|other| other == point)?;
+
+        Some(bucket.swap_remove(i))
+    }
+
+    /// Moves the first point in the bucket at `old_index` matching `predicate` to the bucket at its
+    /// current position, returning the new index.
+    ///
+    /// Costs O(bucket length). If the point is now out of bounds, it is left where it was and
+    /// `None` is returned.
+    pub fn update_position(
+        &mut self,
+        old_index: [usize; 2],
+        predicate: impl FnMut(&T) -> bool,
+    ) -> Option<[usize; 2]>
+    where
+        T: Into<Point2<f32>> + Clone,
+    {
+        let bucket = self.points.get(old_index)?;
+        let i = bucket.iter().position(predicate)?;
+
+        let new_index = self.index_of(bucket[i].clone().into())?;
+
+        if new_index != old_index {
+            let point = self.points[old_index].swap_remove(i);
+            self.points[new_index].push(point);
+        }
+
+        Some(new_index)
+    }
+
     #[must_use]
     pub fn index_of(&self, position: Point2<f32>) -> Option<[usize; 2]> {
         if !self.check_bounds_of(position) {
@@ -195,6 +444,18 @@ impl<T> PointSet<T> {
         position: Point2<f32>,
         radius: f32,
     ) -> Option<impl Iterator<Item = [usize; 2]>> {
+        let [x_range, y_range] = self.bucket_ranges_near_to(position, radius)?;
+
+        Some(x_range.flat_map(move |x| y_range.clone().map(move |y| [x, y])))
+    }
+
+    /// The ranges of bucket indecies along each axis which [`Self::indecies_near_to`] iterates
+    /// over
+    fn bucket_ranges_near_to(
+        &self,
+        position: Point2<f32>,
+        radius: f32,
+    ) -> Option<[Range<usize>; 2]> {
         if !self.is_within_radius(position, radius) {
             return None;
         }
@@ -225,9 +486,7 @@ impl<T> PointSet<T> {
         let max_y = max_y.unwrap_or(self.buckets()[1] - 1);
 
         #[allow(clippy::range_plus_one)]
-        let [x_range, y_range] = [min_x..max_x + 1, min_y..max_y + 1];
-
-        Some(x_range.flat_map(move |x| y_range.clone().map(move |y| [x, y])))
+        Some([min_x..max_x + 1, min_y..max_y + 1])
     }
 
     #[must_use]
@@ -271,6 +530,25 @@ impl<T> PointSet<T> {
         )
     }
 
+    /// Mutable version of [`Self::iter_near`]. Slices the buckets by the ranges of
+    /// [`Self::indecies_near_to`] directly, so that nothing borrows `self` while the buckets are
+    /// borrowed mutably.
+    #[must_use]
+    pub fn iter_near_mut(
+        &mut self,
+        position: Point2<f32>,
+        radius: f32,
+    ) -> Option<impl Iterator<Item = &mut T>> {
+        let [x_range, y_range] = self.bucket_ranges_near_to(position, radius)?;
+
+        Some(
+            self.points
+                .slice_mut(s![x_range, y_range])
+                .into_iter()
+                .flatten(),
+        )
+    }
+
     #[must_use]
     pub fn buckets(&self) -> [usize; 2] {
         self.points.dim().into()
@@ -296,6 +574,13 @@ pub trait AddOffset {
     fn add_offset(&mut self, offset: Vector2<f32>);
 }
 
+/// Moves where the star field is drawn, for when the world is moved back to the origin
+impl AddOffset for StarField {
+    fn add_offset(&mut self, offset: Vector2<f32>) {
+        self.offset += offset;
+    }
+}
+
 impl<T> AddOffset for PointSet<T>
 where
     T: AddOffset,
@@ -310,3 +595,36 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_near_mut_visits_the_same_points_as_iter_near() {
+        let mut point_set = PointSet::new([8, 8], 1.0, Vector2::from([0.0, 0.0]));
+
+        for x in 0..16 {
+            for y in 0..16 {
+                let _ = point_set.insert(Point2::from([x as f32 * 0.5, y as f32 * 0.5]));
+            }
+        }
+
+        let position = Point2::from([3.2, 5.7]);
+
+        let near = point_set
+            .iter_near(position, 1.5)
+            .unwrap()
+            .copied()
+            .collect::<Vec<_>>();
+        let near_mut = point_set
+            .iter_near_mut(position, 1.5)
+            .unwrap()
+            .map(|point| *point)
+            .collect::<Vec<_>>();
+
+        assert!(!near.is_empty());
+        assert_eq!(near.len(), near_mut.len());
+        assert!(near.iter().all(|point| near_mut.contains(point)));
+    }
+}
@@ -1,14 +1,30 @@
+use std::{
+    f32::consts::{FRAC_PI_6, PI, TAU},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
 use macroquad::{
     camera::Camera2D,
-    color::Color,
+    color::{Color, colors},
     input,
     math::{Vec2, vec2},
-    shapes, window,
+    shapes,
+    text::{self, TextParams},
+    window,
 };
 use ndarray::{Array2, Dimension};
 
 use crate::soft_body::BoundingBox;
 
+/// How many world units tall the view is when lines are drawn exactly as thick as they are given.
+/// See [`set_line_scale_for_zoom`]
+pub const REFERENCE_SCREEN_HEIGHT: f32 = 10.0;
+pub const MINIMUM_LINE_SCALE: f32 = 0.5;
+pub const MAXIMUM_LINE_SCALE: f32 = 4.0;
+
+/// The bits of the `f32` every line drawn with [`draw_line`] is made thicker by
+static LINE_SCALE: AtomicU32 = AtomicU32::new(1f32.to_bits());
+
 #[must_use]
 pub fn exp_decay_cutoff(a: f32, b: f32, decay: f32, dt: f32, cutoff: f32) -> (f32, bool) {
     if (a - b).abs() < cutoff {
@@ -24,6 +40,12 @@ pub fn exp_decay(a: f32, b: f32, decay: f32, dt: f32) -> f32 {
     b + (a - b) * (-decay * dt).exp()
 }
 
+/// The signed angle to turn from `from` to reach `to` the short way around, between -π and π
+#[must_use]
+pub fn angle_difference(from: f32, to: f32) -> f32 {
+    PI - (PI - (to - from)).rem_euclid(TAU)
+}
+
 #[must_use]
 pub const fn lerp(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t
@@ -44,17 +66,91 @@ pub fn update_camera_aspect_ratio(camera: &mut Camera2D) {
 }
 
 pub fn mouse_position(camera: &Camera2D) -> Vec2 {
-    camera.screen_to_world(input::mouse_position().into())
+    screen_to_world(camera, input::mouse_position().into())
+}
+
+/// Converts a position in pixels to world space. Only accurate after
+/// [`update_camera_aspect_ratio`] has been called this frame.
+pub fn screen_to_world(camera: &Camera2D, position: Vec2) -> Vec2 {
+    camera.screen_to_world(position)
+}
+
+/// Converts a position in world space to pixels, for drawing screen space elements over world
+/// objects. See [`screen_to_world`].
+pub fn world_to_screen(camera: &Camera2D, position: Vec2) -> Vec2 {
+    camera.world_to_screen(position)
+}
+
+/// The font size text is rasterized at before [`draw_world_text`] scales it into the world
+const WORLD_TEXT_FONT_SIZE: u16 = 64;
+
+/// Draws `text` centered on `center` in world space, `size` world units tall. The camera's y axis
+/// points up while glyphs are drawn downwards, so they are flipped to stay upright.
+pub fn draw_world_text(text: &str, center: Vec2, size: f32, color: Color) {
+    let scale = size / WORLD_TEXT_FONT_SIZE as f32;
+    let dimensions = text::measure_text(text, None, WORLD_TEXT_FONT_SIZE, scale);
+
+    text::draw_text_ex(
+        text,
+        center.x - dimensions.width / 2.0,
+        center.y - dimensions.height / 2.0,
+        TextParams {
+            font_size: WORLD_TEXT_FONT_SIZE,
+            font_scale: -scale,
+            font_scale_aspect: -1.0,
+            color,
+            ..Default::default()
+        },
+    );
 }
 
 pub fn interpolation_scale(interpolation: f32) -> f32 {
     1.0 / (2.0 * interpolation.powi(2) - 2.0 * interpolation + 1.0)
 }
 
+/// `thickness` is multiplied by [`line_scale`]
 pub fn draw_line(start: Vec2, end: Vec2, thickness: f32, color: Color) {
+    draw_line_unscaled(start, end, thickness * line_scale(), color);
+}
+
+/// Like [`draw_line`], for lines whose thickness already accounts for the zoom
+pub fn draw_line_unscaled(start: Vec2, end: Vec2, thickness: f32, color: Color) {
     shapes::draw_line(start.x, start.y, end.x, end.y, thickness, color);
 }
 
+pub fn line_scale() -> f32 {
+    f32::from_bits(LINE_SCALE.load(Ordering::Relaxed))
+}
+
+pub fn set_line_scale(scale: f32) {
+    LINE_SCALE.store(scale.to_bits(), Ordering::Relaxed);
+}
+
+/// Scales lines so they stay about as many pixels thick as they are at
+/// [`REFERENCE_SCREEN_HEIGHT`] when the view is `screen_height` world units tall, between
+/// [`MINIMUM_LINE_SCALE`] and [`MAXIMUM_LINE_SCALE`]
+pub fn set_line_scale_for_zoom(screen_height: f32) {
+    set_line_scale(
+        (screen_height / REFERENCE_SCREEN_HEIGHT).clamp(MINIMUM_LINE_SCALE, MAXIMUM_LINE_SCALE),
+    );
+}
+
+/// Draws a line from `start` to `end` with a head at `end`
+pub fn draw_arrow(start: Vec2, end: Vec2, thickness: f32, color: Color) {
+    draw_line(start, end, thickness, color);
+
+    let back = (start - end).clamp_length_max(thickness * 4.0);
+
+    for angle in [-FRAC_PI_6, FRAC_PI_6] {
+        draw_line(
+            end,
+            end + Vec2::from_angle(angle).rotate(back),
+            thickness,
+            color,
+        );
+    }
+}
+
 pub fn combine_friction(a: f32, b: f32) -> f32 {
     a * b
 }
@@ -79,6 +175,16 @@ pub fn generate_color_for_spring(force: f32, damping: f32) -> Color {
     }
 }
 
+/// Fades from green when `stress` is `0.0` to red when it is `1.0` or more
+pub fn generate_color_for_stress(stress: f32) -> Color {
+    color_lerp(colors::GREEN, colors::RED, stress.clamp(0.0, 1.0))
+}
+
+/// Fades from blue when `load` is `0.0` to red when it is `1.0` or more
+pub fn generate_color_for_load(load: f32) -> Color {
+    color_lerp(colors::BLUE, colors::RED, load.clamp(0.0, 1.0))
+}
+
 pub fn clamp_sign(value: f32, allow_positive: bool, allow_negative: bool) -> f32 {
     if !allow_positive && value > 0.0 || !allow_negative && value < 0.0 {
         0.0
@@ -121,6 +227,10 @@ pub fn intersection_point_of_line_segments(
 /// CREDIT: Wikipedia: <https://en.wikipedia.org/wiki/Line%E2%80%93line_intersection>
 ///
 /// If the lines intersect, returns `([progress along line segment times coeficient; 2], coeficient)`
+///
+/// Segments which only touch at an endpoint count as intersecting, since the progress along each
+/// line is compared inclusively. Parallel segments, including collinear ones which overlap, never
+/// intersect, because the coeficient is zero.
 pub fn line_segment_intersection(
     [a1, b1]: [Vec2; 2],
     [a2, b2]: [Vec2; 2],
@@ -141,7 +251,8 @@ pub fn line_segment_intersection(
 
     let divisor = divisor.abs();
 
-    if divisor <= f32::EPSILON {
+    // Relative to the lengths, so short segments which aren't parallel still intersect
+    if divisor <= f32::EPSILON * (b1 - a1).length() * (b2 - a2).length() {
         return None;
     }
 
@@ -168,11 +279,7 @@ impl<T: RotateCounterClockwise> RotateCounterClockwise for Array2<T> {
 
 impl<T: RotateCounterClockwise> RotateCounterClockwise for Option<T> {
     fn rotate_counter_clockwise(&self) -> Self {
-        if let Some(inner) = self {
-            Some(inner.rotate_counter_clockwise())
-        } else {
-            None
-        }
+        self.as_ref().map(|inner| inner.rotate_counter_clockwise())
     }
 }
 
@@ -192,19 +299,97 @@ impl<T: RotateClockwise> RotateClockwise for Array2<T> {
 
 impl<T: RotateClockwise> RotateClockwise for Option<T> {
     fn rotate_clockwise(&self) -> Self {
-        if let Some(inner) = self {
-            Some(inner.rotate_clockwise())
-        } else {
-            None
-        }
+        self.as_ref().map(|inner| inner.rotate_clockwise())
     }
 }
 
-pub fn bounding_box_of_camera(camera: &Camera2D) -> BoundingBox {
+/// The area of the world which is on screen. Fits all four corners, so it stays correct if the
+/// camera is rotated.
+pub fn visible_world_rect(camera: &Camera2D) -> BoundingBox {
     BoundingBox::fit_points(&[
-        camera.screen_to_world(vec2(0.0, 0.0)),
-        camera.screen_to_world(vec2(window::screen_width(), 0.0)),
-        camera.screen_to_world(vec2(window::screen_width(), window::screen_height())),
-        camera.screen_to_world(vec2(0.0, window::screen_height())),
+        screen_to_world(camera, vec2(0.0, 0.0)),
+        screen_to_world(camera, vec2(window::screen_width(), 0.0)),
+        screen_to_world(
+            camera,
+            vec2(window::screen_width(), window::screen_height()),
+        ),
+        screen_to_world(camera, vec2(0.0, window::screen_height())),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossing_segments_intersect() {
+        let (point, [t, u]) = intersection_point_of_line_segments(
+            [vec2(-1.0, 0.0), vec2(1.0, 0.0)],
+            [vec2(0.0, -1.0), vec2(0.0, 1.0)],
+        )
+        .unwrap();
+
+        assert!(point.distance(Vec2::ZERO) < 1e-6);
+        assert!((t - 0.5).abs() < 1e-6);
+        assert!((u - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn segments_touching_at_an_endpoint_intersect() {
+        assert!(are_line_segments_intersecting(
+            [vec2(0.0, 0.0), vec2(1.0, 0.0)],
+            [vec2(1.0, 0.0), vec2(1.0, 1.0)],
+        ));
+    }
+
+    #[test]
+    fn separate_segments_dont_intersect() {
+        assert!(!are_line_segments_intersecting(
+            [vec2(0.0, 0.0), vec2(1.0, 0.0)],
+            [vec2(2.0, -1.0), vec2(2.0, 1.0)],
+        ));
+    }
+
+    #[test]
+    fn parallel_segments_dont_intersect() {
+        assert!(!are_line_segments_intersecting(
+            [vec2(0.0, 0.0), vec2(1.0, 0.0)],
+            [vec2(0.0, 0.0001), vec2(1.0, 0.0001)],
+        ));
+    }
+
+    #[test]
+    fn collinear_segments_dont_intersect() {
+        assert!(!are_line_segments_intersecting(
+            [vec2(0.0, 0.0), vec2(2.0, 0.0)],
+            [vec2(1.0, 0.0), vec2(3.0, 0.0)],
+        ));
+    }
+
+    #[test]
+    fn short_crossing_segments_intersect() {
+        let (point, [t, u]) = intersection_point_of_line_segments(
+            [vec2(-0.0001, 0.0), vec2(0.0001, 0.0)],
+            [vec2(0.0, -0.0001), vec2(0.0, 0.0001)],
+        )
+        .unwrap();
+
+        assert!(point.distance(Vec2::ZERO) < 1e-9);
+        assert!((t - 0.5).abs() < 1e-4);
+        assert!((u - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn nearly_parallel_crossing_segments_intersect() {
+        // Crossing in the middle at an angle of about a hundredth of a degree
+        let (point, [t, u]) = intersection_point_of_line_segments(
+            [vec2(0.0, 0.0), vec2(10.0, 0.0)],
+            [vec2(2.0, -0.001), vec2(10.0, 0.0006)],
+        )
+        .unwrap();
+
+        assert!(point.distance(vec2(7.0, 0.0)) < 1e-4, "crossed at {point}");
+        assert!((t - 0.7).abs() < 1e-5, "t is {t}");
+        assert!((u - 0.625).abs() < 1e-4, "u is {u}");
+    }
+}